@@ -18,6 +18,60 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         };
         b.iter_batched(setup, routine, BatchSize::SmallInput)
     });
+
+    c.bench_function("decode decorated 32", |b| {
+        let settings = Settings {
+            decorate: true, 
+            ..Default::default()
+        };
+        let mut data = [0; 32];
+        rng.fill_bytes(&mut data);
+        let encoded = encode_with_settings(data, settings);
+
+        b.iter(|| decode_with_settings(&encoded, settings))
+    });
+
+    c.bench_function("decode 256", |b| {
+        let mut data = [0; 256];
+        rng.fill_bytes(&mut data);
+        let encoded = encode(data);
+
+        b.iter(|| decode(&encoded))
+    });
+
+    c.bench_function("decode decorated 256", |b| {
+        let settings = Settings {
+            decorate: true, 
+            ..Default::default()
+        };
+        let mut data = [0; 256];
+        rng.fill_bytes(&mut data);
+        let encoded = encode_with_settings(data, settings);
+
+        b.iter(|| decode_with_settings(&encoded, settings))
+    });
+
+    c.bench_function("round-trip 65536", |b| {
+        let mut data = vec![0; 65536];
+        rng.fill_bytes(&mut data);
+
+        b.iter(|| decode(encode(&data)))
+    });
+
+    c.bench_function("encode 32", |b| {
+        let mut data = [0; 32];
+        rng.fill_bytes(&mut data);
+
+        b.iter(|| encode(data))
+    });
+
+    c.bench_function("encode 4096", |b| {
+        let mut data = vec![0; 4096];
+        rng.fill_bytes(&mut data);
+        let mut buffer = String::new();
+        
+        b.iter(|| encode_into(&data, Settings::default(), &mut buffer))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
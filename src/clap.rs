@@ -0,0 +1,80 @@
+#![cfg(feature = "clap")]
+
+use alloc::vec::Vec;
+use ::clap::{builder::PossibleValue, ValueEnum};
+use crate::{Checksum, InvalidData};
+
+/// Decodes a command-line argument using the default [decoding settings](crate::DecodeSettings). Requires
+/// the `clap` feature. 
+/// 
+/// This is meant to be used as a [clap](::clap) value parser, such that a malformed argument is rejected
+/// with the reason it couldn't be decoded, e.g. `invalid value 'sive sive' for '<KEY>': Data integrity check
+/// failed`. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use clap::{Arg, Command};
+/// 
+/// let command = Command::new("vault")
+///     .arg(Arg::new("key").value_parser(bunk::parse_bunk));
+/// 
+/// let matches = command.clone().try_get_matches_from(["vault", &bunk::encode(b"aftersun")])?;
+/// assert_eq!(matches.get_one::<Vec<u8>>("key").unwrap(), b"aftersun");
+/// assert!(command.try_get_matches_from(["vault", "sive sive"]).is_err());
+/// # Ok::<(), clap::Error>(())
+/// ```
+pub fn parse_bunk(arg: &str) -> Result<Vec<u8>, InvalidData> {
+    crate::decode(arg)
+}
+
+/// Allows [checksum settings](Checksum) to be passed as command-line arguments, e.g. `--checksum length2`. 
+/// Requires the `clap` feature. 
+/// 
+/// The value of each variant is its name in lowercase. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// use clap::{Arg, Command, builder::EnumValueParser};
+/// 
+/// let command = Command::new("encode")
+///     .arg(Arg::new("checksum").long("checksum").value_parser(EnumValueParser::<Checksum>::new()));
+/// 
+/// let matches = command.try_get_matches_from(["encode", "--checksum", "length2"])?;
+/// assert_eq!(matches.get_one::<Checksum>("checksum"), Some(&Checksum::Length2));
+/// # Ok::<(), clap::Error>(())
+/// ```
+impl ValueEnum for Checksum {
+    fn value_variants<'a>() -> &'a [Checksum] {
+        &[
+            Checksum::Disabled, 
+            Checksum::Length1, 
+            Checksum::Length2, 
+            Checksum::Length3, 
+            Checksum::Length4, 
+            Checksum::Length5, 
+            Checksum::Length6, 
+            Checksum::Length7, 
+            Checksum::Length8, 
+            Checksum::Auto, 
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let name = match self {
+            Checksum::Disabled => "disabled", 
+            Checksum::Length1 => "length1", 
+            Checksum::Length2 => "length2", 
+            Checksum::Length3 => "length3", 
+            Checksum::Length4 => "length4", 
+            Checksum::Length5 => "length5", 
+            Checksum::Length6 => "length6", 
+            Checksum::Length7 => "length7", 
+            Checksum::Length8 => "length8", 
+            Checksum::Auto => "auto", 
+        };
+        Some(PossibleValue::new(name))
+    }
+}
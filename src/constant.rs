@@ -0,0 +1,214 @@
+use core::{fmt, ops::Deref};
+use crate::*;
+
+/// Encodes data at compile time using given [settings](Settings) into a string of capacity `N` bytes. 
+/// 
+/// The result is the same as that of [`encode_with_settings`], but since this is a `const fn`, it can be
+/// used to embed encoded constants, e.g. baked-in identifiers, without any runtime cost. The capacity must
+/// be given explicitly; [`encoded_len`] is always sufficient. Since [`Default::default`] can't be called in
+/// `const` contexts, start from a preset like [`Settings::compact`] instead. 
+/// 
+/// Only the simpler settings are supported: [decoration](Settings::decorate), 
+/// [padding](Settings::pad_syllables), [minimal breaks](Settings::minimal_breaks) and
+/// [chunked checksums](Settings::checksum_chunk) aren't. 
+/// 
+/// # Panics
+/// 
+/// Panics if any of the unsupported settings are enabled, or if the encoded string exceeds the capacity. 
+/// When evaluated at compile time, this fails the build. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{ConstString, Settings};
+/// 
+/// const SETTINGS: Settings = Settings::compact();
+/// const ID: ConstString<{ bunk::encoded_len(4, SETTINGS) }> =
+///     bunk::encode_const(&[14, 64, 1, 15], SETTINGS);
+/// 
+/// assert_eq!(ID.as_str(), bunk::encode_with_settings([14, 64, 1, 15], SETTINGS));
+/// ```
+pub const fn encode_const<const N: usize>(data: &[u8], settings: Settings) -> ConstString<N> {
+    let Settings{
+        word_len: max_word, 
+        min_word_len: min_word, 
+        grouping, 
+        separator, 
+        pad_syllables, 
+        minimal_breaks, 
+        checksum, 
+        checksum_seed, 
+        checksum_order, 
+        checksum_chunk, 
+        decorate, 
+        decoration: _, 
+        uppercase, 
+        smoothing, 
+        seed, 
+        whitening, 
+        alphabet, 
+    } = settings;
+    assert!(!decorate, "decoration isn't supported by encode_const");
+    assert!(pad_syllables.is_none(), "padding isn't supported by encode_const");
+    assert!(!minimal_breaks, "minimal breaks aren't supported by encode_const");
+    assert!(checksum_chunk == 0, "chunked checksums aren't supported by encode_const");
+
+    let max_word = match max_word {
+        None | Some(0) => usize::MAX, 
+        Some(max) => max as usize, 
+    };
+    let min_word = match min_word {
+        Some(min) => min as usize, 
+        None => 0, 
+    };
+    let mut separator_buffer = [0; 4];
+    let separator = separator.encode_utf8(&mut separator_buffer).as_bytes();
+    let mut hash = Fnv1a::new_seeded(checksum_seed);
+    let mut checksum_bytes = [0; 8];
+    let total_len = data.len() + checksum.len_for(data.len());
+
+    let mut out = ConstString::new();
+    let mut previous: Option<u8> = None;
+    let mut word_len: usize = 0;
+    let mut i = 0;
+
+    // mirrors `Stream` and `Sentence`, which can't be evaluated at compile time
+    while i < total_len {
+        let byte = match i < data.len() {
+            true => {
+                hash.update(data[i]);
+                let smoothed = match smoothing {
+                    true => running_code(data[i], i, seed), 
+                    false => data[i], 
+                };
+                match whitening {
+                    Some(key) => whiten(smoothed, i, key), 
+                    None => smoothed, 
+                }
+            }
+            false => {
+                if i == data.len() {
+                    checksum_bytes = hash.bytes(checksum, checksum_order);
+                }
+                checksum_bytes[i - data.len()]
+            }
+        };
+        let syllable = syllables::get(alphabet, byte);
+        let too_long = match grouping {
+            WordGrouping::Variable => i != 0 && word_len >= max_word && word_len >= min_word, 
+            WordGrouping::Fixed(n) => n != 0 && i != 0 && i.is_multiple_of(n as usize), 
+        };
+        let ambiguous = match previous {
+            Some(previous) => {
+                syllables::char_follows(alphabet, syllable[0], syllables::get(alphabet, previous))
+            }
+            None => false, 
+        };
+        if too_long || ambiguous {
+            out.push(separator);
+            word_len = 0;
+        }
+        let mut j = 0;
+
+        while j < syllable.len() {
+            match uppercase {
+                true => out.push(&[syllable[j].to_ascii_uppercase()]), 
+                false => out.push(&[syllable[j]]), 
+            }
+            j += 1;
+        }
+        previous = Some(byte);
+        word_len += 1;
+        i += 1;
+    }
+    out
+}
+
+/// A string of capacity `N` bytes encoded at compile time by [`encode_const`]. 
+/// 
+/// Dereferences to [`str`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{ConstString, Settings};
+/// 
+/// const ENCODED: ConstString<32> = bunk::encode_const(b"aftersun", Settings::compact());
+/// 
+/// assert_eq!(ENCODED.len(), bunk::encode_with_settings(b"aftersun", Settings::compact()).len());
+/// assert!(ENCODED.starts_with(char::is_lowercase));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstString<const N: usize> {
+    /// The bytes of the string, followed by zeroes. 
+    buffer: [u8; N], 
+    /// Length of the string in bytes. 
+    len: usize, 
+}
+
+impl<const N: usize> ConstString<N> {
+    /// Creates an empty string. 
+    const fn new() -> ConstString<N> {
+        ConstString { buffer: [0; N], len: 0 }
+    }
+
+    /// Returns the string as a [`str`]. 
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.buffer.split_at(self.len).0) {
+            Ok(string) => string, 
+            Err(_) => unreachable!(), 
+        }
+    }
+
+    /// Returns the length of the string in bytes. 
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the string is empty. 
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends bytes to the string. 
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the capacity is exceeded. 
+    const fn push(&mut self, bytes: &[u8]) {
+        assert!(self.len + bytes.len() <= N, "encoded string exceeds the capacity of the ConstString");
+        let mut i = 0;
+
+        while i < bytes.len() {
+            self.buffer[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+}
+
+impl<const N: usize> Deref for ConstString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ConstString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for ConstString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for ConstString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
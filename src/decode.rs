@@ -1,5 +1,11 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::ops::Range;
 use thiserror::Error;
 use crate::*;
+use crate::syllables::Lookup;
+
+#[cfg(feature = "std")]
+use std::io;
 
 /// Error type for decoding data. 
 #[derive(Error, Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -8,111 +14,2273 @@ pub enum InvalidData {
     #[error("Unrecognized syllable")]
     Syllable, 
 
+    /// The string contained no syllable characters at all, e.g. if something else entirely was pasted.
+    /// Returned only for non-empty strings; empty strings are handled as described for
+    /// [`InvalidData::TooShort`]. 
+    #[error("No syllables found")]
+    NoSyllables, 
+
     /// The number of syllables was not enough to contain the checksum. Returned only when a checksum is
-    /// used; empty strings are otherwise allowed. 
+    /// used, in which case empty strings are too short for any checksum; they are otherwise allowed and
+    /// decode to empty data. 
     #[error("Encoded data was too short")]
     TooShort, 
 
+    /// The decoded data was empty. Returned only by [`decode_nonempty`], in place of both empty data and
+    /// [`InvalidData::TooShort`] for empty strings. 
+    #[error("Encoded data was empty")]
+    Empty, 
+
+    /// The decoded data exceeded the maximum length. Returned only by [`decode_bounded`]. 
+    #[error("Decoded data exceeded the maximum of {max} bytes")]
+    TooLong {
+        /// The maximum number of payload bytes. 
+        max: usize, 
+    }, 
+
+    /// An uppercase letter was found in the middle of a word. Returned only when
+    /// [strict case](DecodeSettings::strict_case) is enabled. 
+    #[error("Unexpected uppercase letter at byte {offset}")]
+    UnexpectedCase {
+        /// Byte offset of the letter in the decoded string. 
+        offset: usize, 
+    }, 
+
     /// The checksum didn't match that of the decoded data. Returned only when a checksum is used. 
     #[error("Data integrity check failed")]
     Checksum, 
+
+    /// The checksum of a chunk didn't match that of its decoded data. Returned in place of
+    /// [`InvalidData::Checksum`] when [chunked checksums](DecodeSettings::checksum_chunk) are used. 
+    #[error("Data integrity check failed in chunk {chunk}")]
+    ChunkChecksum {
+        /// Index of the first chunk whose checksum didn't match. 
+        chunk: usize, 
+    }, 
+
+    /// The decoded data didn't have the expected length. Returned only when decoding into a fixed-size
+    /// array; see [`decode_array`]. 
+    #[error("Decoded data was {found} bytes long, expected {expected}")]
+    WrongLength {
+        /// The expected number of bytes. 
+        expected: usize, 
+        /// The number of bytes found. 
+        found: usize, 
+    }, 
 }
 
 /// Result of decoding data. 
-pub type Result<T> = std::result::Result<T, InvalidData>;
+pub type Result<T> = core::result::Result<T, InvalidData>;
+
+/// Settings used when decoding. 
+/// 
+/// These must match the corresponding [settings used when encoding](Settings); all other encoding settings
+/// are ignored when decoding. For convenience, decoding settings can be created from the encoding settings
+/// with [`From`], or from only the [checksum settings](Checksum), using the default for all other fields. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DecodeSettings {
+    /// The checksum settings used. Default: [`Checksum::Length1`]. 
+    pub checksum: Checksum, 
+    /// The [checksum seed](Settings::checksum_seed) used. Default: `0`. 
+    pub checksum_seed: u32, 
+    /// The [checksum byte order](Settings::checksum_order) used. Default: [`Endianness::Little`]. 
+    pub checksum_order: Endianness, 
+    /// The [checksum chunk length](Settings::checksum_chunk) used. Default: `0`. 
+    pub checksum_chunk: usize, 
+    /// Whether the data was encoded with [smoothing](Settings::smoothing). Default: `true`. 
+    pub smoothing: bool, 
+    /// The [seed](Settings::seed) used for smoothing. Default: `0`. 
+    pub seed: u64, 
+    /// The key of the [whitening](Settings::whitening) pass used, if any. Default: `None`. 
+    pub whitening: Option<u64>, 
+    /// The [alphabet](Settings::alphabet) used. Default: [`Alphabet::Standard`]. 
+    pub alphabet: Alphabet, 
+    /// Whether the data was encoded with [minimal breaks](Settings::minimal_breaks). Default: `false`. 
+    pub minimal_breaks: bool, 
+    /// Whether to reject uppercase letters anywhere but at the start of a word, as produced by
+    /// [decorating](Settings::decorate), with [`InvalidData::UnexpectedCase`]. This catches letters
+    /// capitalised by mistake when transcribing by hand. Ignored by [`decode_lenient`]. Default: `false`. 
+    pub strict_case: bool, 
+    /// Whether to compare all checksum bytes without exiting early on a mismatch, so that the time taken
+    /// doesn't reveal how many bytes matched. This only hardens the checksum comparison; parsing the
+    /// syllables still takes time dependent on the input. Default: `false`. 
+    pub constant_time: bool, 
+}
+
+impl Default for DecodeSettings {
+    fn default() -> Self {
+        DecodeSettings {
+            checksum: Checksum::default(), 
+            checksum_seed: 0, 
+            checksum_order: Endianness::Little, 
+            checksum_chunk: 0, 
+            smoothing: true, 
+            seed: 0, 
+            whitening: None, 
+            alphabet: Alphabet::default(), 
+            minimal_breaks: false, 
+            strict_case: false, 
+            constant_time: false, 
+        }
+    }
+}
+
+impl From<Checksum> for DecodeSettings {
+    fn from(checksum: Checksum) -> Self {
+        DecodeSettings {
+            checksum, 
+            ..Default::default()
+        }
+    }
+}
+
+impl From<Settings> for DecodeSettings {
+    fn from(settings: Settings) -> Self {
+        DecodeSettings {
+            checksum: settings.checksum, 
+            checksum_seed: settings.checksum_seed, 
+            checksum_order: settings.checksum_order, 
+            checksum_chunk: settings.checksum_chunk, 
+            smoothing: settings.smoothing, 
+            seed: settings.seed, 
+            whitening: settings.whitening, 
+            alphabet: settings.alphabet, 
+            minimal_breaks: settings.minimal_breaks, 
+            strict_case: false, 
+            constant_time: false, 
+        }
+    }
+}
+
+/// Error type for decoding data read from a reader. See [`decode_from_reader`]. 
+/// 
+/// Both kinds of errors convert into it with `?`, and the underlying error is its
+/// [source](std::error::Error::source). [`InvalidData`] remains the error type of everything not reading
+/// from a reader. 
+#[cfg(feature = "std")]
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    /// Reading failed, or the data read was not valid UTF-8. 
+    #[error("Failed to read encoded data")]
+    Io(#[from] io::Error), 
+
+    /// The data read could not be decoded. 
+    #[error("Failed to decode data")]
+    Data(#[from] InvalidData), 
+}
+
+/// Converts into an [`io::Error`], unwrapping [`DecodeError::Io`] and wrapping [`DecodeError::Data`] in an
+/// error of kind [`InvalidData`](io::ErrorKind::InvalidData). 
+#[cfg(feature = "std")]
+impl From<DecodeError> for io::Error {
+    fn from(error: DecodeError) -> io::Error {
+        match error {
+            DecodeError::Io(error) => error, 
+            DecodeError::Data(error) => io::Error::new(io::ErrorKind::InvalidData, error), 
+        }
+    }
+}
+
+/// Decodes a string using the default [decoding settings](DecodeSettings). 
+/// 
+/// Use this only if the default settings affecting decoding were used when encoding the string (e.g., if
+/// [`encode`](encode()) was used). All other [encoding settings](crate::Settings) are ignored when
+/// decoding. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// let encoded = bunk::encode(b"aftersun");
+/// let decoded = bunk::decode(encoded)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode(string: impl AsRef<str>) -> Result<Vec<u8>> {
+    decode_with_settings(string, DecodeSettings::default())
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings). 
+/// 
+/// The settings must match the ones used when the string was encoded. Either the
+/// [encoding settings](crate::Settings) or only the [checksum settings](Checksum) may be passed; see
+/// [`DecodeSettings`]. 
+/// 
+/// Characters that can't be part of a syllable are skipped and end the word they follow. Combining marks
+/// (e.g. accents) and invisible formatting characters (e.g. zero-width joiners and soft hyphens), which
+/// may be introduced when pasting text, are instead ignored entirely, so they don't split a word. Precomposed
+/// accented letters aren't recognized; see [`decode_normalized`]. 
+/// 
+/// # Examples
+/// 
+/// Disabled checksum: 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Disabled, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// let decoded = bunk::decode_with_settings(encoded, settings.checksum)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+/// 
+/// Disabled [smoothing](crate::Settings::smoothing): 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let settings = Settings {
+///     smoothing: false, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// let decoded = bunk::decode_with_settings(encoded, settings)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_with_settings<S>(string: impl AsRef<str>, settings: S) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_mono(string.as_ref(), settings.into())
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings), also returning the number of syllables
+/// parsed. 
+/// 
+/// The count includes the syllables encoding the checksum, so it's useful for showing progress through an
+/// encoded string of known length, e.g. when entered by hand. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// let (decoded, syllables) = bunk::decode_counted(encoded, Checksum::Length1)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// assert_eq!(syllables, 9);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_counted<S>(string: impl AsRef<str>, settings: S) -> Result<(Vec<u8>, usize)>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_counted_mono(string.as_ref(), settings.into())
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings), rejecting empty data. 
+/// 
+/// Returns [`InvalidData::Empty`] if the string is empty or decodes to no data, regardless of the checksum
+/// settings. Otherwise equivalent to [`decode_with_settings`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData};
+/// 
+/// let empty = bunk::encode([]);
+/// 
+/// assert_eq!(bunk::decode_nonempty(&empty, Checksum::Length1), Err(InvalidData::Empty));
+/// assert_eq!(bunk::decode_nonempty("", Checksum::Length1), Err(InvalidData::Empty));
+/// assert_eq!(bunk::decode_nonempty("", Checksum::Disabled), Err(InvalidData::Empty));
+/// ```
+pub fn decode_nonempty<S>(string: impl AsRef<str>, settings: S) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_nonempty_mono(string.as_ref(), settings.into())
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings), rejecting data longer than
+/// `max_payload` bytes. 
+/// 
+/// Decoding stops with [`InvalidData::TooLong`] as soon as more syllables are found than the payload and
+/// checksum could take up, so at most `max_payload` plus [`Checksum::len`] bytes are ever allocated. This
+/// bounds the memory used when decoding strings from untrusted sources. The bound applies to the payload
+/// only; the checksum bytes are allowed on top of it. Otherwise equivalent to [`decode_with_settings`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData};
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// 
+/// assert_eq!(bunk::decode_bounded(&encoded, Checksum::default(), 8)?, b"aftersun");
+/// assert_eq!(bunk::decode_bounded(&encoded, Checksum::default(), 7), Err(InvalidData::TooLong { max: 7 }));
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_bounded<S>(string: impl AsRef<str>, settings: S, max_payload: usize) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_bounded_mono(string.as_ref(), settings.into(), max_payload)
+}
+
+/// Decodes a string encoded with [`encode_delimiterless`](crate::encode_delimiterless) using given
+/// [decoding settings](DecodeSettings). 
+/// 
+/// A word break is assumed before every uppercase letter, so the string must not have been case-folded in
+/// transit. Any other characters are skipped as usual. Otherwise equivalent to [`decode_with_settings`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let encoded = bunk::encode_delimiterless([14, 64, 1, 15], Settings::default());
+/// 
+/// assert_eq!(bunk::decode_delimiterless(&encoded, Checksum::default())?, [14, 64, 1, 15]);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_delimiterless<S>(string: impl AsRef<str>, settings: S) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_delimiterless_mono(string.as_ref(), settings.into())
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings) into an existing buffer. 
+/// 
+/// The buffer is cleared before decoding, but its capacity is reused. This is useful when decoding many
+/// strings in a loop, since it avoids allocating a new buffer for each one. If an error is returned, the
+/// buffer is left empty. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let mut buffer = Vec::new();
+/// 
+/// for data in [b"after", b"sunny"] {
+///     let encoded = bunk::encode(data);
+///     bunk::decode_into(encoded, Checksum::default(), &mut buffer)?;
+/// 
+///     assert_eq!(buffer, data);
+/// }
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_into<S>(string: impl AsRef<str>, settings: S, out: &mut Vec<u8>) -> Result<()>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_into_mono(string.as_ref(), settings.into(), out)
+}
+
+/// Decodes each of a list of strings using given [decoding settings](DecodeSettings). 
+/// 
+/// Each string is decoded independently, so the result of each is preserved; a string failing to decode
+/// doesn't affect the others. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData};
+/// 
+/// let items = [bunk::encode(b"after"), String::from("?!"), bunk::encode(b"sun")];
+/// let decoded = bunk::decode_batch(&items, Checksum::default());
+/// 
+/// assert_eq!(decoded, [Ok(b"after".to_vec()), Err(InvalidData::NoSyllables), Ok(b"sun".to_vec())]);
+/// ```
+pub fn decode_batch<T>(items: &[T], settings: impl Into<DecodeSettings>) -> Vec<Result<Vec<u8>>>
+where
+    T: AsRef<str>
+{
+    let settings = settings.into();
+    items
+        .iter()
+        .map(|item| decode_mono(item.as_ref(), settings))
+        .collect()
+}
+
+/// Decodes each of a list of strings using given [decoding settings](DecodeSettings) in parallel. 
+/// 
+/// Equivalent to [`decode_batch`], but the strings are distributed over the threads of the global
+/// [rayon](https://docs.rs/rayon) thread pool. Requires the `rayon` feature. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let data: Vec<_> = (0..1000u32).map(u32::to_le_bytes).collect();
+/// let items = bunk::encode_batch(&data, Settings::default());
+/// let decoded = bunk::par_decode_batch(&items, Checksum::default());
+/// 
+/// assert_eq!(decoded, bunk::decode_batch(&items, Checksum::default()));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_decode_batch<T>(items: &[T], settings: impl Into<DecodeSettings>) -> Vec<Result<Vec<u8>>>
+where
+    T: AsRef<str> + Sync
+{
+    use rayon::prelude::*;
+
+    let settings = settings.into();
+    items
+        .par_iter()
+        .map(|item| decode_mono(item.as_ref(), settings))
+        .collect()
+}
+
+/// Decodes each of a number of strings joined by a separator character using given
+/// [decoding settings](DecodeSettings). 
+/// 
+/// The string is split on every occurrence of the separator, like [`str::split`], and each segment is
+/// decoded independently as with [`decode_batch`]. This is the inverse of joining independently encoded
+/// strings with the separator, so an empty string, or a leading or trailing separator, yields an empty
+/// segment. 
+/// 
+/// The separator must not occur in any of the encoded strings, so it mustn't be a letter, the
+/// [word separator](crate::Settings::separator) or, when [decorating](crate::Settings::decorate), part of
+/// the [decoration](crate::Decoration) --- in particular not a period or comma. Characters like `'|'`,
+/// `';'`, `'/'` and `'\n'` are good choices. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let joined = [bunk::encode(b"after"), bunk::encode(b"sun")].join("|");
+/// let decoded = bunk::decode_multi(&joined, Checksum::default(), '|');
+/// 
+/// assert_eq!(decoded, [Ok(b"after".to_vec()), Ok(b"sun".to_vec())]);
+/// ```
+pub fn decode_multi<S>(string: impl AsRef<str>, settings: S, separator: char) -> Vec<Result<Vec<u8>>>
+where
+    S: Into<DecodeSettings>
+{
+    let settings = settings.into();
+    string
+        .as_ref()
+        .split(separator)
+        .map(|segment| decode_mono(segment, settings))
+        .collect()
+}
+
+/// Reads a string from a reader and decodes it using given [decoding settings](DecodeSettings). 
+/// 
+/// The reader is read until EOF before decoding. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// let decoded = bunk::decode_from_reader(encoded.as_bytes(), Checksum::default())?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::DecodeError>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_from_reader<R, S>(mut reader: R, settings: S) -> std::result::Result<Vec<u8>, DecodeError>
+where
+    R: io::Read, 
+    S: Into<DecodeSettings>, 
+{
+    let mut string = String::new();
+    reader.read_to_string(&mut string)?;
+    let decoded = decode_mono(&string, settings.into())?;
+    Ok(decoded)
+}
+
+/// Decodes a string read from an iterator of characters using given [decoding settings](DecodeSettings). 
+/// 
+/// The characters are decoded one word at a time as they are read, so the string is never collected in its
+/// entirety, and an error is returned as soon as an unrecognized syllable is found. This is useful for
+/// decoding interactive input. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// let decoded = bunk::decode_chars(encoded.chars(), Checksum::default())?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_chars<I, S>(chars: I, settings: S) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = char>, 
+    S: Into<DecodeSettings>, 
+{
+    // factored out non-generic code to reduce code size
+    decode_chars_mono(&mut chars.into_iter(), settings.into())
+}
+
+/// Monomorphised char decode implementation. 
+#[inline(never)]
+fn decode_chars_mono(chars: &mut dyn Iterator<Item = char>, settings: DecodeSettings) -> Result<Vec<u8>> {
+    let mut decoder = Decoder::new(settings);
+    let mut word = String::new();
+
+    for char in chars {
+        word.push(char);
+
+        // feed complete words to the decoder along with the delimiter ending them
+        if !settings.alphabet.is_syllable_char(char) && !syllables::is_ignorable(char) {
+            decoder.push(&word)?;
+            word.clear();
+        }
+    }
+    decoder.push(&word)?;
+    decoder.finish()
+}
+
+/// Decodes a string without knowing which [checksum settings](Checksum) were used when encoding it. 
+/// 
+/// Each fixed checksum length is tried, from the longest to the shortest, and the first one that matches is
+/// returned along with the decoded data. All other [decoding settings](DecodeSettings) are assumed to be the
+/// default. [`Checksum::Auto`] is never returned: it yields the same bytes as the fixed length it picks for
+/// the payload, so a string encoded with it is reported as having that length. 
+/// 
+/// Note that this is less reliable than decoding with known settings: a checksum of length `n` matches
+/// random data with a probability of 1 in 256<sup>n</sup>, so a string encoded with a long checksum may be
+/// mistaken as having a short one if it has been corrupted, and short checksums are more likely to match by
+/// chance. Strings encoded with [`Checksum::Disabled`] can never be detected and yield
+/// [`InvalidData::Checksum`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Length3, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// let (decoded, checksum) = bunk::decode_autodetect(encoded)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// assert_eq!(checksum, Checksum::Length3);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_autodetect(string: impl AsRef<str>) -> Result<(Vec<u8>, Checksum)> {
+    // factored out non-generic code to reduce code size
+    decode_autodetect_mono(string.as_ref())
+}
+
+/// Monomorphised autodetecting decode implementation. 
+#[inline(never)]
+fn decode_autodetect_mono(string: &str) -> Result<(Vec<u8>, Checksum)> {
+    const CHECKSUMS: [Checksum; 8] = [
+        Checksum::Length8, 
+        Checksum::Length7, 
+        Checksum::Length6, 
+        Checksum::Length5, 
+        Checksum::Length4, 
+        Checksum::Length3, 
+        Checksum::Length2, 
+        Checksum::Length1, 
+    ];
+    let mut raw = Vec::with_capacity(string.len() / 2);
+    parse(string, Alphabet::Standard, false, &mut raw)?;
+
+    for checksum in CHECKSUMS {
+        let mut buffer = raw.clone();
+
+        if unpack(&mut buffer, checksum.into()).is_ok() {
+            return Ok((buffer, checksum))
+        }
+    }
+    match raw.is_empty() {
+        true => Err(InvalidData::TooShort), 
+        false => Err(InvalidData::Checksum), 
+    }
+}
+
+/// Decoded data along with its checksum. See [`decode_verbose`]. 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DecodeOutput {
+    /// The decoded payload bytes. 
+    pub payload: Vec<u8>, 
+    /// The checksum bytes found at the end of the encoded string. 
+    pub checksum_bytes: Vec<u8>, 
+    /// Whether the checksum matched that of the payload. 
+    pub checksum_ok: bool, 
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings), returning the checksum found in the
+/// string along with the payload. 
+/// 
+/// Unlike [`decode_with_settings`], a checksum mismatch is not treated as an error; instead, the payload is
+/// returned with [`DecodeOutput::checksum_ok`] set to `false`, leaving it to the caller to decide how to
+/// handle it. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Length2, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// let output = bunk::decode_verbose(encoded, settings)?;
+/// 
+/// assert_eq!(output.payload, b"aftersun");
+/// assert_eq!(output.checksum_bytes.len(), 2);
+/// assert!(output.checksum_ok);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_verbose(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<DecodeOutput> {
+    // factored out non-generic code to reduce code size
+    decode_verbose_mono(string.as_ref(), settings.into())
+}
+
+/// Monomorphised verbose decode implementation. 
+#[inline(never)]
+fn decode_verbose_mono(string: &str, settings: DecodeSettings) -> Result<DecodeOutput> {
+    let mut payload = Vec::with_capacity(string.len() / 2);
+    check_case(string, settings.alphabet, settings, 0)?;
+    parse(string, settings.alphabet, settings.minimal_breaks, &mut payload)?;
+    
+    let (payload_len, corrupt) = unpack_payload(&mut payload, settings)?;
+    let checksum_bytes = payload.split_off(payload_len);
+    let checksum_ok = corrupt.is_none();
+
+    Ok(DecodeOutput {
+        payload, 
+        checksum_bytes, 
+        checksum_ok, 
+    })
+}
+
+/// Statistics of a decoded string, for diagnosing unexpected output. See [`decode_stats`]. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DecodeStats {
+    /// The number of payload bytes decoded. 
+    pub payload_len: usize, 
+    /// The number of syllables parsed, including those of the checksum. 
+    pub syllable_count: usize, 
+    /// The number of words, i.e., runs of characters that may be part of a syllable. 
+    pub word_count: usize, 
+    /// The number of characters skipped between syllables, including the spaces between words. Many more of
+    /// these than words indicates mangled input. 
+    pub skipped_chars: usize, 
+    /// Whether the string appears to be [decorated](Settings::decorate), i.e., contains periods or commas, or
+    /// starts with a capitalised word that isn't all uppercase. 
+    pub decorated: bool, 
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings) and returns statistics of how it was
+/// parsed, discarding the decoded data. 
+/// 
+/// Returns the same errors as [`decode_with_settings`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, DecodeStats};
+/// 
+/// let stats = bunk::decode_stats("Nargepa caldytis. Progoper.", Checksum::Disabled)?;
+/// 
+/// assert_eq!(stats, DecodeStats {
+///     payload_len: 9, 
+///     syllable_count: 9, 
+///     word_count: 3, 
+///     skipped_chars: 4, 
+///     decorated: true, 
+/// });
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_stats(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<DecodeStats> {
+    // factored out non-generic code to reduce code size
+    decode_stats_mono(string.as_ref(), settings.into())
+}
+
+/// Monomorphised decode statistics implementation. 
+#[inline(never)]
+fn decode_stats_mono(string: &str, settings: DecodeSettings) -> Result<DecodeStats> {
+    let (payload, syllable_count) = decode_counted_mono(string, settings)?;
+    let syllable_char = |char| settings.alphabet.is_syllable_char(char);
+    let word_count = string
+        .split(|char| !syllable_char(char))
+        .filter(|word| !word.is_empty())
+        .count();
+    let skipped_chars = string
+        .chars()
+        .filter(|&char| !syllable_char(char))
+        .count();
+    let capitalised = string
+        .chars()
+        .find(|&char| syllable_char(char))
+        .is_some_and(char::is_uppercase) && string.chars().any(char::is_lowercase);
+
+    Ok(DecodeStats {
+        payload_len: payload.len(), 
+        syllable_count, 
+        word_count, 
+        skipped_chars, 
+        decorated: capitalised || string.contains(['.', ',']), 
+    })
+}
+
+/// The textual structure of an encoded string: its words, their syllables, and the delimiters between them.
+/// See [`parse_structure`]. 
+/// 
+/// All spans are byte ranges in the parsed string, and together with the [leading](Structure::leading)
+/// delimiter and the [delimiter](Word::delimiter) following each word, the words cover the whole string. 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Structure {
+    /// Characters preceding the first word, if any. 
+    pub leading: Range<usize>, 
+    /// The words of the string, in order. 
+    pub words: Vec<Word>, 
+}
+
+/// A word of an encoded string, i.e., a run of characters that may be part of a syllable. See [`Structure`]. 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Word {
+    /// Span of the word. 
+    pub span: Range<usize>, 
+    /// The syllables of the word, in order. 
+    pub syllables: Vec<SyllableSpan>, 
+    /// Characters following the word up until the next one or the end of the string, e.g. a space, or a comma
+    /// or period when [decorated](crate::Settings::decorate). 
+    pub delimiter: Range<usize>, 
+}
+
+/// A syllable of an encoded string. See [`Structure`]. 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SyllableSpan {
+    /// Span of the syllable, including any ignorable characters within it, such as soft hyphens. 
+    pub span: Range<usize>, 
+    /// The raw byte encoded by the syllable, i.e., before [smoothing](crate::Settings::smoothing) is undone, 
+    /// or `None` if the rest of the word doesn't start with a syllable, in which case the span covers it. 
+    pub index: Option<u8>, 
+}
+
+/// Parses the textual structure of a string encoded with the [standard alphabet](Alphabet::Standard) into
+/// its words, syllables, and the delimiters between them, without decoding it. 
+/// 
+/// Unlike decoding, this preserves the text rather than recovering the data, which is useful for e.g.
+/// syntax highlighting or editing encoded strings. Every string can be parsed: the syllables are found
+/// greedily like when decoding, and should the rest of a word not start with a syllable, it's kept as a
+/// single unrecognized span. The number of words is `words.len()`. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// let encoded = "Nargepa caldytis. Progoper.";
+/// let structure = bunk::parse_structure(encoded);
+/// 
+/// assert_eq!(structure.words.len(), 3);
+/// assert_eq!(&encoded[structure.words[1].span.clone()], "caldytis");
+/// assert_eq!(&encoded[structure.words[1].delimiter.clone()], ". ");
+/// 
+/// let syllables: Vec<&str> = structure.words[0].syllables
+///     .iter()
+///     .map(|syllable| &encoded[syllable.span.clone()])
+///     .collect();
+/// assert_eq!(syllables, ["Nar", "ge", "pa"]);
+/// ```
+pub fn parse_structure(encoded: impl AsRef<str>) -> Structure {
+    // factored out non-generic code to reduce code size
+    parse_structure_mono(encoded.as_ref())
+}
+
+/// Monomorphised structure parsing implementation. 
+#[inline(never)]
+fn parse_structure_mono(string: &str) -> Structure {
+    let table = Alphabet::Standard;
+    let syllable_char = |char| table.is_syllable_char(char);
+    let next_word = |from: usize| {
+        string[from..]
+            .find(syllable_char)
+            .map_or(string.len(), |index| from + index)
+    };
+    let leading = 0..next_word(0);
+    let mut words = Vec::new();
+    let mut start = leading.end;
+
+    while start < string.len() {
+        // the word continues through ignorable characters, but not ones trailing it
+        let rest = &string[start..];
+        let run = rest
+            .find(|char| !syllable_char(char) && !syllables::is_ignorable(char))
+            .unwrap_or(rest.len());
+        let end = rest[..run]
+            .char_indices()
+            .rfind(|&(_, char)| syllable_char(char))
+            .map_or(start, |(index, char)| start + index + char.len_utf8());
+
+        // find the syllables like the parser would
+        let mut indices = Indices::new(&string[start..end], table, false);
+        let mut syllables = Vec::new();
+
+        while !indices.string.is_empty() {
+            let offset = end - indices.string.len();
+            let prefix = match indices.ascii {
+                true => indices.prefix_of(indices.string), 
+                false => indices.prefix_ignoring(), 
+            };
+            let Some((index, length)) = prefix else {
+                syllables.push(SyllableSpan { span: offset..end, index: None });
+                break
+            };
+            syllables.push(SyllableSpan { span: offset..offset + length, index: Some(index) });
+            indices.string = &indices.string[length..];
+        }
+        let next = next_word(end);
+        words.push(Word { span: start..end, syllables, delimiter: end..next });
+        start = next;
+    }
+    Structure { leading, words }
+}
+
+/// Verifies that a string can be decoded using given [decoding settings](DecodeSettings) and that its
+/// checksum matches, without allocating the decoded data. 
+/// 
+/// This is cheaper than [`decode_with_settings`] and returns the same errors, making it useful for
+/// validating input before committing to decoding it. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData};
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// 
+/// assert_eq!(bunk::verify(&encoded, Checksum::default()), Ok(()));
+/// assert_eq!(bunk::verify("faevlesa", Checksum::default()), Err(InvalidData::Syllable));
+/// ```
+pub fn verify(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<()> {
+    // factored out non-generic code to reduce code size
+    verify_mono(string.as_ref(), settings.into())
+}
+
+/// Monomorphised verify implementation. 
+#[inline(never)]
+fn verify_mono(string: &str, settings: DecodeSettings) -> Result<()> {
+    check_case(string, settings.alphabet, settings, 0)?;
+    let mut verifier = Verifier::new(settings);
+
+    for index in Indices::new(string, settings.alphabet, settings.minimal_breaks) {
+        verifier.push(index?);
+    }
+    verifier.finish(|_| ())
+}
+
+/// Verifies the checksum of a string read from a reader using given [decoding settings](DecodeSettings), 
+/// without storing the string or the decoded data. 
+/// 
+/// The reader is read in chunks, each of which is parsed up until its last complete word and fed to the
+/// checksum, like a [`Decoder`] would. Memory use is thus constant, barring extremely long words. Returns
+/// `Ok(false)` if the checksum doesn't match, and an error if reading fails, the data isn't valid UTF-8, or
+/// it can't be decoded for any other reason. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode([33; 10_000]);
+/// 
+/// assert!(bunk::verify_reader(encoded.as_bytes(), Checksum::default())?);
+/// assert!(!bunk::verify_reader(&b"sive sive"[..], Checksum::default())?);
+/// # Ok::<(), bunk::DecodeError>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn verify_reader<R, S>(mut reader: R, settings: S) -> std::result::Result<bool, DecodeError>
+where
+    R: io::Read, 
+    S: Into<DecodeSettings>, 
+{
+    // factored out non-generic code to reduce code size
+    verify_reader_mono(&mut reader, settings.into())
+}
+
+/// Monomorphised reader verify implementation. 
+#[cfg(feature = "std")]
+#[inline(never)]
+fn verify_reader_mono(
+    reader: &mut dyn io::Read, 
+    settings: DecodeSettings, 
+) -> std::result::Result<bool, DecodeError> {
+    let mut state = ReaderState::new(settings);
+    let mut chunk = [0; 4096];
+    // the decoded bytes are discarded
+    let mut decoded = Vec::new();
+
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break, 
+            Ok(read) => read, 
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue, 
+            Err(error) => return Err(error.into()), 
+        };
+        state.push(&chunk[..read], &mut decoded)?;
+        decoded.clear();
+    }
+    match state.finish(&mut decoded) {
+        Ok(()) => Ok(true), 
+        Err(DecodeError::Data(InvalidData::Checksum | InvalidData::ChunkChecksum { .. })) => Ok(false), 
+        Err(error) => Err(error), 
+    }
+}
+
+/// State of decoding a string read from a reader in chunks. See [`verify_reader`] and [`DecodeReader`]. 
+/// 
+/// Each chunk is parsed up until its last complete word and fed to a [`Verifier`], so that the decoded
+/// payload bytes are produced as they're read. 
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct ReaderState {
+    /// Verifier decoding the syllables parsed so far. 
+    verifier: Verifier, 
+    /// Bytes read but not yet parsed: the last word, and possibly a character split between chunks. 
+    pending: Vec<u8>, 
+    /// Byte offset of the pending bytes in the whole string. 
+    offset: usize, 
+}
+
+#[cfg(feature = "std")]
+impl ReaderState {
+    /// Creates a state using given [decoding settings](DecodeSettings). 
+    fn new(settings: DecodeSettings) -> ReaderState {
+        ReaderState {
+            verifier: Verifier::new(settings), 
+            pending: Vec::new(), 
+            offset: 0, 
+        }
+    }
+
+    /// Parses the complete part of the bytes read so far followed by a chunk, appending the decoded payload
+    /// bytes to `out`. 
+    fn push(&mut self, chunk: &[u8], out: &mut Vec<u8>) -> std::result::Result<(), DecodeError> {
+        self.pending.extend_from_slice(chunk);
+
+        let text = match core::str::from_utf8(&self.pending) {
+            Ok(text) => text, 
+            // a character may be continued by the next chunk
+            Err(error) if error.error_len().is_none() => {
+                core::str::from_utf8(&self.pending[..error.valid_up_to()]).map_err(|_| utf8_error())?
+            }
+            Err(_) => return Err(utf8_error().into()), 
+        };
+        let complete = complete_len(text, self.verifier.settings.alphabet);
+        feed(&text[..complete], self.offset, &mut self.verifier, out)?;
+        self.pending.drain(..complete);
+        self.offset += complete;
+        Ok(())
+    }
+
+    /// Parses the bytes remaining at the end of the string and verifies the checksum, appending the decoded
+    /// payload bytes to `out`. 
+    fn finish(mut self, out: &mut Vec<u8>) -> std::result::Result<(), DecodeError> {
+        let text = core::str::from_utf8(&self.pending).map_err(|_| utf8_error())?;
+        let alphabet = self.verifier.settings.alphabet;
+
+        // like the parser, reject strings consisting only of non-syllable characters
+        let empty = skip_delimiters(text, alphabet).is_empty();
+
+        if self.verifier.count == 0 && empty && self.offset + text.len() != 0 {
+            return Err(InvalidData::NoSyllables.into())
+        }
+        feed(text, self.offset, &mut self.verifier, out)?;
+        self.verifier.finish(|byte| out.push(byte))?;
+        Ok(())
+    }
+}
+
+/// Feeds the syllables of a parsable part of a string at given byte offset to a verifier, appending the
+/// decoded payload bytes to `out`. 
+#[cfg(feature = "std")]
+fn feed(text: &str, offset: usize, verifier: &mut Verifier, out: &mut Vec<u8>) -> Result<()> {
+    let settings = verifier.settings;
+    check_case(text, settings.alphabet, settings, offset)?;
+
+    if skip_delimiters(text, settings.alphabet).is_empty() {
+        return Ok(())
+    }
+    for index in Indices::new(text, settings.alphabet, settings.minimal_breaks) {
+        out.extend(verifier.push(index?));
+    }
+    Ok(())
+}
+
+/// Returns the error for data read not being valid UTF-8. 
+#[cfg(feature = "std")]
+fn utf8_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+/// Decodes at most the first `max_bytes` bytes of a string using given [decoding settings](DecodeSettings). 
+/// 
+/// Only the syllables needed are parsed, which is useful for peeking at e.g. a version prefix of a long
+/// string. Since the checksum is at the end, this bypasses integrity checking entirely; the bytes returned
+/// may be wrong without an error being reported. [Strict case](DecodeSettings::strict_case) is not checked
+/// either. The [checksum settings](Checksum) are still used to tell the checksum apart from the payload, so
+/// that only payload bytes are returned when the payload is shorter than `max_bytes`. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"v2:it's such a beautiful day");
+/// 
+/// assert_eq!(bunk::decode_prefix(&encoded, Checksum::default(), 3)?, b"v2:");
+/// assert_eq!(bunk::decode_prefix(&encoded, Checksum::default(), 100)?, b"v2:it's such a beautiful day");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_prefix<S>(string: impl AsRef<str>, settings: S, max_bytes: usize) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_prefix_mono(string.as_ref(), settings.into(), max_bytes)
+}
+
+/// Monomorphised prefix decode implementation. 
+#[inline(never)]
+fn decode_prefix_mono(string: &str, settings: DecodeSettings, max_bytes: usize) -> Result<Vec<u8>> {
+    // parse past the prefix by the length of the checksum to tell whether the string ends within the prefix, 
+    // in which case the last bytes parsed are the checksum
+    let parse_len = settings.checksum.chunked_len(max_bytes, settings.checksum_chunk);
+    let mut buffer = Vec::with_capacity(parse_len.min(string.len()));
+
+    for index in Indices::new(string, settings.alphabet, settings.minimal_breaks).take(parse_len) {
+        buffer.push(index?);
+    }
+    let payload_len = match buffer.len() == parse_len {
+        true => max_bytes, 
+        false => payload_len(buffer.len(), settings)?, 
+    };
+    // remove the checksums of chunks, after which the last checksum is at the end
+    if settings.checksum_chunk != 0 {
+        let frame_len = frame_len(settings);
+        let mut position = 0;
+
+        buffer.retain(|_| {
+            position += 1;
+            (position - 1) % frame_len < settings.checksum_chunk
+        });
+    }
+    buffer.truncate(payload_len);
+    restore(&mut buffer, 0, settings);
+    Ok(buffer)
+}
+
+/// Decodes two strings using given [decoding settings](DecodeSettings) and counts the bytes differing
+/// between them. 
+/// 
+/// Bytes are compared position by position; if one payload is longer than the other, each extra byte counts
+/// as differing. Returns an error if either string can't be decoded. This is useful for showing how far off
+/// an entered string is from an expected one, rather than just whether they match. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let a = bunk::encode(b"aftersun");
+/// let b = bunk::encode(b"afterSUN");
+/// 
+/// assert_eq!(bunk::payload_diff(&a, &b, Checksum::default())?, 3);
+/// assert_eq!(bunk::payload_diff(&a, bunk::encode(b"after"), Checksum::default())?, 3);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn payload_diff<S>(a: impl AsRef<str>, b: impl AsRef<str>, settings: S) -> Result<usize>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    payload_diff_mono(a.as_ref(), b.as_ref(), settings.into())
+}
+
+/// Monomorphised payload diff implementation. 
+#[inline(never)]
+fn payload_diff_mono(a: &str, b: &str, settings: DecodeSettings) -> Result<usize> {
+    let a = decode_mono(a, settings)?;
+    let b = decode_mono(b, settings)?;
+    let differing = a
+        .iter()
+        .zip(&b)
+        .filter(|(a, b)| a != b)
+        .count();
+    Ok(differing + a.len().abs_diff(b.len()))
+}
+
+/// A correction made when decoding leniently. See [`decode_lenient`]. 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Correction {
+    /// Byte offset of the unrecognized text in the decoded string. 
+    pub offset: usize, 
+    /// The unrecognized text. 
+    pub original: String, 
+    /// The syllable it was replaced with. 
+    pub syllable: &'static str, 
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings), correcting unrecognized syllables. 
+/// 
+/// When text not matching any syllable is found, it is replaced with the syllable closest to it by edit
+/// distance, and decoding continues. All corrections made are returned along with the decoded data.
+/// Non-alphabetic characters at the start of the string are also skipped. 
+/// 
+/// This is intended to recover from typos in manually transcribed strings. Since the corrections are only
+/// guesses, they may well be wrong; the [checksum](Checksum) is then the only safety net, so using this
+/// without a checksum is not recommended. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// assert_eq!(encoded, "nargepa caldytis progoper");
+/// 
+/// // the "a" in "cal" is mistyped as "q"
+/// let (decoded, corrections) = bunk::decode_lenient("nargepa cqldytis progoper", Checksum::default())?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// assert_eq!(corrections[0].original, "cql");
+/// assert_eq!(corrections[0].syllable, "cal");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_lenient<S>(string: impl AsRef<str>, settings: S) -> Result<(Vec<u8>, Vec<Correction>)>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_lenient_mono(string.as_ref(), settings.into())
+}
+
+/// Monomorphised lenient decode implementation. 
+#[inline(never)]
+fn decode_lenient_mono(string: &str, settings: DecodeSettings) -> Result<(Vec<u8>, Vec<Correction>)> {
+    let mut buffer = Vec::with_capacity(string.len() / 2);
+    let mut corrections = Vec::new();
+
+    if skip_delimiters(string, settings.alphabet).is_empty() && !string.is_empty() {
+        return Err(InvalidData::NoSyllables)
+    }
+    // matches syllables like the strict decoders do, ignoring ignorable characters within them
+    let mut indices = Indices::new(string, settings.alphabet, settings.minimal_breaks);
+
+    while !indices.string.is_empty() {
+        let rest = indices.string;
+
+        // find the longest valid syllable at the beginning of the string, or the closest one if there is none
+        let (index, length) = match indices.prefix() {
+            Some(found) => found, 
+            None => {
+                let (index, length) = syllables::closest_prefix_of(settings.alphabet, rest)
+                    .expect("The string starts with a syllable character");
+                corrections.push(Correction {
+                    offset: string.len() - rest.len(), 
+                    original: rest[..length].to_owned(), 
+                    syllable: syllables::get_str(settings.alphabet, index), 
+                });
+                (index, length)
+            }
+        };
+        buffer.push(index);
+
+        // gobble until start of next syllable
+        indices.string = skip_delimiters(&rest[length..], settings.alphabet);
+    }
+    unpack(&mut buffer, settings)?;
+    Ok((buffer, corrections))
+}
+
+/// Maximum number of syllables matched by [`decode_all`] when exploring the segmentations of a string. 
+const MAX_MATCHES: usize = 1 << 16;
+
+/// Decodes a string using given [decoding settings](DecodeSettings) in every way it can be split into
+/// syllables, returning all interpretations whose [checksum](Checksum) is valid. 
+/// 
+/// Although the encoder inserts word-breaks to avoid ambiguities, a hand-typed string missing a needed
+/// break --- or one stripped of spaces altogether --- may be split into syllables in more than one way.
+/// Whereas [`decode_with_settings`] always picks the longest syllable, this explores each alternative within
+/// every word. The checksum usually disambiguates to a single interpretation; without one, every
+/// segmentation is returned. Each interpretation is returned only once. 
+/// 
+/// Since the number of segmentations may grow exponentially with the length of a word, the search is
+/// bounded to 65536 syllable matches; for long strings with many ambiguities, some interpretations may
+/// therefore be missed. Strings that can't be decoded at all yield no interpretations. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// let decoded = bunk::decode_all(encoded.replace(' ', ""), Checksum::default());
+/// 
+/// assert!(decoded.contains(&b"aftersun".to_vec()));
+/// ```
+pub fn decode_all<S>(string: impl AsRef<str>, settings: S) -> Vec<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_all_mono(string.as_ref(), settings.into())
+}
+
+/// Monomorphised exhaustive decode implementation. 
+#[inline(never)]
+fn decode_all_mono(string: &str, settings: DecodeSettings) -> Vec<Vec<u8>> {
+    let table = settings.alphabet;
+
+    if check_case(string, table, settings, 0).is_err() {
+        return Vec::new()
+    }
+    // the letters of the string, and whether each of them ends a word
+    let mut letters = Vec::with_capacity(string.len());
+    let mut ends = Vec::with_capacity(string.len());
+
+    for char in string.chars() {
+        if syllables::is_ignorable(char) {
+            continue
+        }
+        if !table.is_syllable_char(char) {
+            if let Some(end) = ends.last_mut() {
+                *end = true;
+            }
+        } else if char.is_ascii() {
+            letters.push(char as u8);
+            ends.push(false);
+        } else {
+            // no syllable contains non-ascii letters
+            return Vec::new()
+        }
+    }
+    if let Some(end) = ends.last_mut() {
+        *end = true;
+    }
+    let letters = core::str::from_utf8(&letters).expect("The letters are ascii");
+
+    // finds the syllable of a given length at a position, should there be one within the word
+    let syllable_at = |position: usize, len: usize| {
+        let end = position + len;
+
+        if end > letters.len() || ends[position..end - 1].contains(&true) {
+            return None
+        }
+        match table.longest_prefix_of(&letters[position..end]) {
+            Some((index, length)) if length == len => Some(index), 
+            _ => None, 
+        }
+    };
+    let mut interpretations = Vec::new();
+    let mut buffer = Vec::new();
+    // the position and length of each syllable in the buffer
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    let mut position = 0;
+    let mut min_len = 1;
+    let mut matches = 0;
+
+    // depth-first search of the segmentations, trying shorter syllables first
+    loop {
+        if position == letters.len() {
+            // distinct segmentations yield distinct syllables, and so distinct interpretations
+            let mut candidate = buffer.clone();
+
+            if unpack(&mut candidate, settings).is_ok() {
+                interpretations.push(candidate);
+            }
+        } else {
+            let next = (min_len..=syllables::MAX_LEN)
+                .find_map(|len| syllable_at(position, len).map(|index| (index, len)));
+
+            if let Some((index, len)) = next {
+                matches += 1;
+
+                if matches > MAX_MATCHES {
+                    break
+                }
+                buffer.push(index);
+                stack.push((position, len));
+                position += len;
+                min_len = 1;
+                continue
+            }
+        }
+        // backtrack to the previous syllable and try a longer one in its place
+        match stack.pop() {
+            Some((start, len)) => {
+                buffer.pop();
+                position = start;
+                min_len = len + 1;
+            }
+            None => break, 
+        }
+    }
+    interpretations
+}
+
+/// Default mapping of lookalike characters to the ascii letters they're commonly mistaken for, used with
+/// [`decode_normalized`]. 
+/// 
+/// | Lookalikes | Letter |
+/// | ---------- | ------ |
+/// | `0`        | `o`    |
+/// | `1`        | `l`    |
+/// | `5`        | `s`    |
+/// | `àáâãäå`   | `a`    |
+/// | `ç`        | `c`    |
+/// | `èéêë`     | `e`    |
+/// | `ìíîï`     | `i`    |
+/// | `ñ`        | `n`    |
+/// | `òóôõöø`   | `o`    |
+/// | `ùúûü`     | `u`    |
+/// | `ýÿ`       | `y`    |
+/// 
+/// Uppercase lookalikes are mapped as their lowercase counterparts. 
+pub const LOOKALIKES: &[(char, char)] = &[
+    ('0', 'o'), ('1', 'l'), ('5', 's'), 
+    ('à', 'a'), ('á', 'a'), ('â', 'a'), ('ã', 'a'), ('ä', 'a'), ('å', 'a'), 
+    ('ç', 'c'), 
+    ('è', 'e'), ('é', 'e'), ('ê', 'e'), ('ë', 'e'), 
+    ('ì', 'i'), ('í', 'i'), ('î', 'i'), ('ï', 'i'), 
+    ('ñ', 'n'), 
+    ('ò', 'o'), ('ó', 'o'), ('ô', 'o'), ('õ', 'o'), ('ö', 'o'), ('ø', 'o'), 
+    ('ù', 'u'), ('ú', 'u'), ('û', 'u'), ('ü', 'u'), 
+    ('ý', 'y'), ('ÿ', 'y'), 
+];
+
+/// Decodes a string using given [decoding settings](DecodeSettings), after replacing lookalike characters
+/// with the letters they're mistaken for. 
+/// 
+/// This recovers from e.g. `0`/`o` confusions and accented letters inserted by autocorrect when
+/// transcribing by hand. The mapping is given as pairs of `(lookalike, letter)`; [`LOOKALIKES`] is a
+/// sensible default. Lookalikes are matched ignoring case. Characters that are already
+/// [ascii syllable characters](Settings::alphabet) are never replaced, so digits are kept as-is for
+/// alphabets that include them. Offsets in errors refer to the normalized string. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Checksum;
+/// 
+/// let encoded = bunk::encode(b"aftersun");
+/// assert_eq!(encoded, "nargepa caldytis progoper");
+/// 
+/// let transcribed = "nårgepa cäldytis pr0goper";
+/// let decoded = bunk::decode_normalized(transcribed, Checksum::default(), bunk::LOOKALIKES)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_normalized<S>(
+    string: impl AsRef<str>, 
+    settings: S, 
+    lookalikes: &[(char, char)], 
+) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_normalized_mono(string.as_ref(), settings.into(), lookalikes)
+}
+
+/// Monomorphised normalized decode implementation. 
+#[inline(never)]
+fn decode_normalized_mono(
+    string: &str, 
+    settings: DecodeSettings, 
+    lookalikes: &[(char, char)], 
+) -> Result<Vec<u8>> {
+    let normalize = |char: char| {
+        if char.is_ascii() && settings.alphabet.is_syllable_char(char) {
+            return char
+        }
+        let lower = char.to_lowercase().next().unwrap_or(char);
+        lookalikes
+            .iter()
+            .find(|&&(lookalike, _)| lookalike == char || lookalike == lower)
+            .map(|&(_, letter)| letter)
+            .unwrap_or(char)
+    };
+    let normalized: String = string
+        .chars()
+        .map(normalize)
+        .collect();
+    decode_mono(&normalized, settings)
+}
+
+/// Decodes a string using given [decoding settings](DecodeSettings) into a fixed-size array. 
+/// 
+/// This avoids allocating memory on the heap, which is useful when the length of the data is known, e.g. for
+/// keys. Returns [`InvalidData::WrongLength`] if the decoded data isn't exactly `N` bytes long. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData};
+/// 
+/// let encoded = bunk::encode([33, 14, 224, 134]);
+/// let decoded: [u8; 4] = bunk::decode_array(&encoded, Checksum::default())?;
+/// 
+/// assert_eq!(decoded, [33, 14, 224, 134]);
+/// assert_eq!(
+///     bunk::decode_array::<8>(&encoded, Checksum::default()), 
+///     Err(InvalidData::WrongLength { expected: 8, found: 4 }), 
+/// );
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_array<const N: usize>(
+    string: impl AsRef<str>, 
+    settings: impl Into<DecodeSettings>, 
+) -> Result<[u8; N]> {
+    let mut array = [0; N];
+    let found = decode_slice_mono(string.as_ref(), settings.into(), &mut array)?;
+
+    match found == N {
+        true => Ok(array), 
+        false => Err(InvalidData::WrongLength { expected: N, found }), 
+    }
+}
+
+/// Decodes a `u128` encoded with [`encode_u128`](crate::encode_u128) using given
+/// [decoding settings](DecodeSettings). 
+/// 
+/// The decoded data must be exactly 16 bytes, which are read in little-endian order. Returns
+/// [`InvalidData::WrongLength`] otherwise. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData, Settings};
+/// 
+/// let encoded = bunk::encode_u128(u128::MAX - 1, Settings::default());
+/// 
+/// assert_eq!(bunk::decode_u128(&encoded, Checksum::default())?, u128::MAX - 1);
+/// assert_eq!(
+///     bunk::decode_u128(bunk::encode([0; 8]), Checksum::default()), 
+///     Err(InvalidData::WrongLength { expected: 16, found: 8 }), 
+/// );
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_u128(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<u128> {
+    decode_array(string, settings).map(u128::from_le_bytes)
+}
+
+/// Decodes a `u64` encoded with [`encode_u64`](crate::encode_u64) using given
+/// [decoding settings](DecodeSettings). 
+/// 
+/// The decoded data must be exactly 8 bytes, which are read in little-endian order. Returns
+/// [`InvalidData::WrongLength`] otherwise. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData, Settings};
+/// 
+/// let encoded = bunk::encode_u64(u64::MAX - 1, Settings::default());
+/// 
+/// assert_eq!(bunk::decode_u64(&encoded, Checksum::default())?, u64::MAX - 1);
+/// assert_eq!(
+///     bunk::decode_u64(bunk::encode([0; 16]), Checksum::default()), 
+///     Err(InvalidData::WrongLength { expected: 8, found: 16 }), 
+/// );
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn decode_u64(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<u64> {
+    decode_array(string, settings).map(u64::from_le_bytes)
+}
+
+/// Decodes a [UUID](uuid::Uuid) encoded with [`encode_uuid`](crate::encode_uuid) using given
+/// [decoding settings](DecodeSettings). Requires the `uuid` feature. 
+/// 
+/// The decoded data must be exactly 16 bytes, which are read in big-endian order as by
+/// [`Uuid::from_bytes`](uuid::Uuid::from_bytes). Returns [`InvalidData::WrongLength`] otherwise. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, InvalidData, Settings};
+/// use uuid::Uuid;
+/// 
+/// let uuid = Uuid::from_bytes([33, 14, 224, 134, 231, 6, 39, 34, 97, 102, 116, 101, 114, 115, 117, 110]);
+/// let encoded = bunk::encode_uuid(uuid, Settings::default());
+/// 
+/// assert_eq!(bunk::decode_uuid(&encoded, Checksum::default())?, uuid);
+/// assert_eq!(
+///     bunk::decode_uuid(bunk::encode([0; 8]), Checksum::default()), 
+///     Err(InvalidData::WrongLength { expected: 16, found: 8 }), 
+/// );
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[cfg(feature = "uuid")]
+pub fn decode_uuid(string: impl AsRef<str>, settings: impl Into<DecodeSettings>) -> Result<uuid::Uuid> {
+    decode_array(string, settings).map(uuid::Uuid::from_bytes)
+}
+
+/// Decodes a string into a slice, returning the length of the decoded data. Bytes not fitting in the slice
+/// are counted but discarded. 
+#[inline(never)]
+fn decode_slice_mono(string: &str, settings: DecodeSettings, out: &mut [u8]) -> Result<usize> {
+    check_case(string, settings.alphabet, settings, 0)?;
+    let mut verifier = Verifier::new(settings);
+    let mut len = 0;
+
+    let mut write = |byte| {
+        if let Some(slot) = out.get_mut(len) {
+            *slot = byte;
+        }
+        len += 1;
+    };
+    for index in Indices::new(string, settings.alphabet, settings.minimal_breaks) {
+        if let Some(byte) = verifier.push(index?) {
+            write(byte);
+        }
+    }
+    verifier.finish(write)?;
+    Ok(len)
+}
+
+/// Decodes a string into a newly allocated buffer. 
+fn decode_mono(string: &str, settings: DecodeSettings) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(string.len() / 2);
+    decode_into_mono(string, settings, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Monomorphised decode implementation counting the syllables parsed. 
+#[inline(never)]
+fn decode_counted_mono(string: &str, settings: DecodeSettings) -> Result<(Vec<u8>, usize)> {
+    let mut buffer = Vec::with_capacity(string.len() / 2);
+    check_case(string, settings.alphabet, settings, 0)?;
+    parse(string, settings.alphabet, settings.minimal_breaks, &mut buffer)?;
+
+    // one byte is parsed per syllable, including those of the checksum
+    let syllables = buffer.len();
+    unpack(&mut buffer, settings)?;
+    Ok((buffer, syllables))
+}
+
+/// Monomorphised decode implementation rejecting long data. 
+#[inline(never)]
+fn decode_bounded_mono(string: &str, settings: DecodeSettings, max_payload: usize) -> Result<Vec<u8>> {
+    let max_len = settings.checksum.chunked_len(max_payload, settings.checksum_chunk);
+    let mut buffer = Vec::with_capacity(max_len.min(string.len() / 2));
+    check_case(string, settings.alphabet, settings, 0)?;
+
+    for index in Indices::new(string, settings.alphabet, settings.minimal_breaks) {
+        if buffer.len() == max_len {
+            return Err(InvalidData::TooLong { max: max_payload })
+        }
+        buffer.push(index?);
+    }
+    unpack(&mut buffer, settings)?;
+    Ok(buffer)
+}
+
+/// Monomorphised delimiterless decode implementation. 
+#[inline(never)]
+fn decode_delimiterless_mono(string: &str, settings: DecodeSettings) -> Result<Vec<u8>> {
+    let mut spaced = String::with_capacity(string.len() * 2);
+
+    for char in string.chars() {
+        if char.is_uppercase() {
+            spaced.push(' ');
+        }
+        spaced.push(char);
+    }
+    decode_mono(&spaced, settings)
+}
+
+/// Monomorphised decode implementation rejecting empty data. 
+#[inline(never)]
+fn decode_nonempty_mono(string: &str, settings: DecodeSettings) -> Result<Vec<u8>> {
+    match decode_mono(string, settings) {
+        Ok(data) if data.is_empty() => Err(InvalidData::Empty), 
+        Err(InvalidData::TooShort) if string.is_empty() => Err(InvalidData::Empty), 
+        result => result, 
+    }
+}
+
+/// Monomorphised decode implementation. 
+#[inline(never)]
+fn decode_into_mono(string: &str, settings: DecodeSettings, out: &mut Vec<u8>) -> Result<()> {
+    out.clear();
+    let result = check_case(string, settings.alphabet, settings, 0)
+        .and_then(|_| parse(string, settings.alphabet, settings.minimal_breaks, out))
+        .and_then(|_| unpack(out, settings));
+
+    // make sure no partially decoded data is left in the buffer
+    if result.is_err() {
+        out.clear();
+    }
+    result
+}
+
+/// Decodes a string encoded with a custom syllable [table](Table) using given
+/// [decoding settings](DecodeSettings). 
+/// 
+/// The table must be the same as the one used when encoding; see [`encode_with_table`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings, Table};
+/// 
+/// let syllables: [String; 256] = std::array::from_fn(|i| format!("x{}", bunk::syllable(i as u8)));
+/// let table = Table::from_syllables(std::array::from_fn(|i| syllables[i].as_str()))?;
+/// 
+/// let encoded = bunk::encode_with_table(b"aftersun", &table, Settings::default());
+/// let decoded = bunk::decode_with_table(&encoded, &table, Checksum::default())?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn decode_with_table<S>(string: impl AsRef<str>, table: &Table, settings: S) -> Result<Vec<u8>>
+where
+    S: Into<DecodeSettings>
+{
+    // factored out non-generic code to reduce code size
+    decode_table_mono(string.as_ref(), table, settings.into())
+}
+
+/// Monomorphised decode implementation for custom tables. 
+#[inline(never)]
+fn decode_table_mono(string: &str, table: &Table, settings: DecodeSettings) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(string.len() / 2);
+    check_case(string, table, settings, 0)?;
+    parse(string, table, settings.minimal_breaks, &mut buffer)?;
+    unpack(&mut buffer, settings)?;
+    Ok(buffer)
+}
 
-/// Decodes a string using the default [checksum settings](Checksum). 
+/// Incrementally decodes a string fed to it in fragments. 
 /// 
-/// Use this only if the default checksum setting was used when encoding the string (e.g., if
-/// [`encode`](encode()) was used). All other [encoding settings](crate::Settings) are ignored when
-/// decoding. 
+/// This is useful when the encoded string isn't available all at once, e.g. when it is received over a
+/// network. Fragments may be split at arbitrary character boundaries --- even in the middle of a syllable
+/// --- and yield the same result as decoding the concatenated string with [`decode_with_settings`]. 
 /// 
 /// # Examples
 /// 
 /// ```
+/// use bunk::{Checksum, Decoder};
+/// 
 /// let encoded = bunk::encode(b"aftersun");
-/// let decoded = bunk::decode(encoded)?;
+/// let (first, second) = encoded.split_at(5);
+/// 
+/// let mut decoder = Decoder::new(Checksum::default());
+/// decoder.push(first)?;
+/// decoder.push(second)?;
+/// let decoded = decoder.finish()?;
 /// 
 /// assert_eq!(decoded, b"aftersun");
 /// # Ok::<(), bunk::InvalidData>(())
 /// ```
-pub fn decode(string: impl AsRef<str>) -> Result<Vec<u8>> {
-    decode_with_settings(string, Checksum::default())
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    /// Trailing fragment that has not been parsed yet since it may be continued by the next fragment. 
+    tail: String, 
+    /// Raw bytes decoded so far. These are still run-encoded and may have a checksum at the end. 
+    buffer: Vec<u8>, 
+    /// Byte offset of the tail in the whole string. 
+    offset: usize, 
+    /// The settings used. 
+    settings: DecodeSettings, 
+}
+
+impl Decoder {
+    /// Creates a decoder using given [decoding settings](DecodeSettings). 
+    pub fn new(settings: impl Into<DecodeSettings>) -> Decoder {
+        Decoder {
+            tail: String::new(), 
+            buffer: Vec::new(), 
+            offset: 0, 
+            settings: settings.into(), 
+        }
+    }
+
+    /// Decodes a fragment of the string. 
+    /// 
+    /// Only complete words are decoded; a word at the end of the fragment is retained until the next
+    /// fragment or [`Decoder::finish`]. Returns an error as soon as an unrecognized syllable is found. 
+    pub fn push(&mut self, mut fragment: &str) -> Result<()> {
+        // the parser skips non-syllable characters preceding a syllable, which has to be mirrored here when
+        // they're at the start of a fragment
+        let alphabet = self.settings.alphabet;
+
+        if self.tail.is_empty() {
+            let trimmed = fragment.trim_start_matches(|char| !alphabet.is_syllable_char(char));
+            self.offset += fragment.len() - trimmed.len();
+            fragment = trimmed;
+        }
+        self.tail.push_str(fragment);
+
+        let complete = complete_len(&self.tail, alphabet);
+        check_case(&self.tail[..complete], alphabet, self.settings, self.offset)?;
+        parse(&self.tail[..complete], alphabet, self.settings.minimal_breaks, &mut self.buffer)?;
+        self.tail.drain(..complete);
+        self.offset += complete;
+        Ok(())
+    }
+
+    /// Decodes the remainder of the string, verifies the checksum, should there be one, and returns the
+    /// decoded data. 
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        // like the parser, reject strings consisting only of non-syllable characters
+        if self.buffer.is_empty() && self.tail.is_empty() && self.offset != 0 {
+            return Err(InvalidData::NoSyllables)
+        }
+        check_case(&self.tail, self.settings.alphabet, self.settings, self.offset)?;
+        parse(&self.tail, self.settings.alphabet, self.settings.minimal_breaks, &mut self.buffer)?;
+        unpack(&mut self.buffer, self.settings)?;
+        Ok(self.buffer)
+    }
 }
 
-/// Decodes a string using given [checksum settings](Checksum). 
+/// Adapter reading an encoded string from an inner [`io::Read`] and decoding it using given
+/// [decoding settings](DecodeSettings), such that reading from it yields the decoded data. 
 /// 
-/// The checksum setting must match the one used when the string was encoded. All other
-/// [encoding settings](crate::Settings) are ignored when decoding. 
+/// This lets decoding act as a transparent filter in existing [`io::Read`] pipelines, e.g. with
+/// [`io::copy`]. The inner reader is read in chunks, each of which is parsed up until its last complete word
+/// like a [`Decoder`] would, so memory use is constant, barring extremely long words. Decoded bytes are
+/// yielded as soon as they're known not to be part of the checksum, which is verified at EOF. 
+/// 
+/// Data that can't be decoded, including a checksum that doesn't match, is reported as an [`io::Error`] of
+/// kind [`InvalidData`](io::ErrorKind::InvalidData) wrapping the [`InvalidData`] error; see
+/// [`DecodeError`]. Since the checksum is only verified at the end, the data read before it may be
+/// corrupt, and must be discarded should an error occur. Reading after an error yields no more data. 
 /// 
 /// # Examples
 /// 
-/// Disabled checksum: 
 /// ```
-/// use bunk::{Checksum, Settings};
+/// use std::io::Read;
+/// use bunk::{Checksum, DecodeReader};
 /// 
-/// let settings = Settings {
-///     checksum: Checksum::Disabled, 
-///     ..Default::default()
-/// };
-/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
-/// let decoded = bunk::decode_with_settings(encoded, settings.checksum)?;
+/// let encoded = bunk::encode(b"aftersun");
+/// let mut reader = DecodeReader::new(encoded.as_bytes(), Checksum::default());
+/// let mut decoded = Vec::new();
+/// reader.read_to_end(&mut decoded)?;
 /// 
 /// assert_eq!(decoded, b"aftersun");
-/// # Ok::<(), bunk::InvalidData>(())
+/// 
+/// let mut reader = DecodeReader::new(&b"sive sive"[..], Checksum::default());
+/// assert!(reader.read_to_end(&mut decoded).is_err());
+/// # Ok::<(), std::io::Error>(())
 /// ```
-pub fn decode_with_settings(string: impl AsRef<str>, checksum: Checksum) -> Result<Vec<u8>> {
-    // factored out non-generic code to reduce code size
-    decode_mono(string.as_ref(), checksum)
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct DecodeReader<R> {
+    /// Reader the encoded string is read from. 
+    reader: R, 
+    /// State of the decoding, or `None` once EOF has been reached or an error has occurred. 
+    state: Option<ReaderState>, 
+    /// Buffer the encoded string is read into. 
+    chunk: Vec<u8>, 
+    /// Decoded bytes not yet read. 
+    decoded: Vec<u8>, 
+    /// Number of decoded bytes already read. 
+    position: usize, 
 }
 
-/// Monomorphised decode implementation. 
-#[inline(never)]
-fn decode_mono(mut string: &str, checksum: Checksum) -> Result<Vec<u8>> {
-    let mut buffer = Vec::with_capacity(string.len() / 2);
+#[cfg(feature = "std")]
+impl<R: io::Read> DecodeReader<R> {
+    /// Creates an adapter decoding the string read from given reader using given
+    /// [decoding settings](DecodeSettings). 
+    pub fn new(reader: R, settings: impl Into<DecodeSettings>) -> DecodeReader<R> {
+        DecodeReader {
+            reader, 
+            state: Some(ReaderState::new(settings.into())), 
+            chunk: alloc::vec![0; 4096], 
+            decoded: Vec::new(), 
+            position: 0, 
+        }
+    }
+
+    /// Returns a reference to the inner reader. 
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the inner reader. Data read from it but not yet decoded is lost. 
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read> io::Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0)
+        }
+        // read and decode chunks until there are decoded bytes to return, or the end is reached
+        while self.position == self.decoded.len() {
+            self.decoded.clear();
+            self.position = 0;
+
+            let Some(state) = &mut self.state else {
+                return Ok(0)
+            };
+            let result = match self.reader.read(&mut self.chunk) {
+                Ok(0) => self.state.take().map_or(Ok(()), |state| state.finish(&mut self.decoded)), 
+                Ok(read) => state.push(&self.chunk[..read], &mut self.decoded), 
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue, 
+                Err(error) => Err(error.into()), 
+            };
+            if let Err(error) = result {
+                self.state = None;
+                self.decoded.clear();
+                return Err(error.into())
+            }
+        }
+        let decoded = &self.decoded[self.position..];
+        let len = decoded.len().min(buf.len());
+        buf[..len].copy_from_slice(&decoded[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Returns the length of the part of a fragment that can be parsed without knowing what follows it. 
+/// 
+/// Syllables never span non-syllable characters, so everything up until the last one can be parsed. This
+/// excludes [ignorable](syllables::is_ignorable) characters, which may be within a syllable. 
+fn complete_len(fragment: &str, table: impl Lookup) -> usize {
+    fragment
+        .char_indices()
+        .rfind(|&(_, char)| !table.is_syllable_char(char) && !syllables::is_ignorable(char))
+        .map(|(index, char)| index + char.len_utf8())
+        .unwrap_or(0)
+}
+
+/// Checks that only the first letter of each word is uppercase, should
+/// [strict case](DecodeSettings::strict_case) be enabled. The offset of the string in the whole decoded
+/// string is added to the offset of any error. 
+fn check_case(string: &str, table: impl Lookup, settings: DecodeSettings, offset: usize) -> Result<()> {
+    if !settings.strict_case {
+        return Ok(())
+    }
+    let mut initial = true;
+
+    for (index, char) in string.char_indices() {
+        if syllables::is_ignorable(char) {
+            continue
+        }
+        let syllable_char = table.is_syllable_char(char);
+
+        if syllable_char && !initial && char.is_uppercase() {
+            return Err(InvalidData::UnexpectedCase { offset: offset + index })
+        }
+        initial = !syllable_char;
+    }
+    Ok(())
+}
+
+/// Parses the syllables of a string and pushes their indices to a buffer. 
+/// 
+/// The pushed bytes are still run-encoded and may have a checksum at the end; see [`unpack`]. Syllables are
+/// matched by backtracking if the string was encoded with [minimal breaks](Settings::minimal_breaks). 
+fn parse(string: &str, table: impl Lookup, backtrack: bool, buffer: &mut Vec<u8>) -> Result<()> {
+    for index in Indices::new(string, table, backtrack) {
+        buffer.push(index?);
+    }
+    Ok(())
+}
+
+/// Iterator over the indices of the syllables in a string. 
+/// 
+/// The indices are still run-encoded and may have a checksum at the end. Yields an error if an unrecognized
+/// syllable is found, after which iteration ends. 
+struct Indices<'a, L = Alphabet> {
+    /// The rest of the string. 
+    string: &'a str, 
+    /// The syllable table used. 
+    table: L, 
+    /// Whether syllables are matched by [backtracking](Lookup::backtracking_prefix_of). 
+    backtrack: bool, 
+    /// Whether the string is all ascii, in which case it can't contain
+    /// [ignorable](syllables::is_ignorable) characters. 
+    ascii: bool, 
+    /// Buffer of the letters of the current syllable with any ignorable characters removed, reused between
+    /// syllables. Only used if the string isn't all ascii. 
+    letters: String, 
+    /// The length of the string up to and including each of the buffered letters. 
+    ends: Vec<usize>, 
+}
+
+impl<'a, L: Lookup> Indices<'a, L> {
+    /// Creates an iterator over the syllables of a string, skipping any delimiters leading the first
+    /// syllable. A string without syllable characters is kept as-is, so that it's rejected. 
+    fn new(string: &'a str, table: L, backtrack: bool) -> Indices<'a, L> {
+        let string = match skip_delimiters(string, table) {
+            "" => string, 
+            skipped => skipped, 
+        };
+        Indices {
+            string, 
+            table, 
+            backtrack, 
+            ascii: string.is_ascii(), 
+            letters: String::new(), 
+            ends: Vec::new(), 
+        }
+    }
+
+    /// Finds the syllable at the beginning of a string. 
+    /// 
+    /// Returns `(syllable_index, syllable_length)`. 
+    fn prefix_of(&self, string: &str) -> Option<(u8, usize)> {
+        match self.backtrack {
+            true => self.table.backtracking_prefix_of(string), 
+            false => self.table.longest_prefix_of(string), 
+        }
+    }
+
+    /// Like [`Indices::prefix_of`] for the rest of the string, but skipping
+    /// [ignorable](syllables::is_ignorable) characters within the word. The length is that in the string,
+    /// including any ignorable characters within the syllable. 
+    fn prefix_ignoring(&mut self) -> Option<(u8, usize)> {
+        self.letters.clear();
+        self.ends.clear();
 
-    // decode raw bytes from string. the bytes are still run-encoded and may have a checksum at the end
-    while !string.is_empty() {
+        // no syllable is longer than the longest one, so the rest of the word needn't be collected
+        for (index, char) in self.string.char_indices() {
+            if self.letters.len() == self.table.max_len() {
+                break
+            }
+            if self.table.is_syllable_char(char) && char.is_ascii() {
+                self.letters.push(char);
+                self.ends.push(index + 1);
+            } else if !syllables::is_ignorable(char) {
+                break
+            }
+        }
+        self.prefix_of(&self.letters).map(|(index, length)| (index, self.ends[length - 1]))
+    }
+
+    /// Finds the syllable at the beginning of the rest of the string, skipping any ignorable characters
+    /// within it. 
+    /// 
+    /// Returns `(syllable_index, syllable_length)`. 
+    fn prefix(&mut self) -> Option<(u8, usize)> {
+        match self.ascii {
+            true => self.prefix_of(self.string), 
+            false => self.prefix_ignoring(), 
+        }
+    }
+}
+
+impl<L: Lookup> Iterator for Indices<'_, L> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Result<u8>> {
+        if self.string.is_empty() {
+            return None
+        }
         // find the longest valid syllable at the beginning of the string
-        let (index, length) = syllables::longest_prefix_of(string)
-            .ok_or(InvalidData::Syllable)?;
+        let Some((index, length)) = self.prefix() else {
+            // only strings without syllable characters are kept starting with a delimiter
+            let error = match skip_delimiters(self.string, self.table) {
+                "" => InvalidData::NoSyllables, 
+                _ => InvalidData::Syllable, 
+            };
+            self.string = "";
+            return Some(Err(error))
+        };
+
+        // gobble until start of next syllable
+        self.string = skip_delimiters(&self.string[length..], self.table);
 
         // the index of the syllable is its payload
-        buffer.push(index);
+        Some(Ok(index))
+    }
+}
 
-        // gobble until start of next syllable
-        string = &string[length..];
-        string = string
-            .find(char::is_alphabetic)
+/// Skips the non-syllable characters at the start of a string. 
+fn skip_delimiters(string: &str, table: impl Lookup) -> &str {
+    // delimiters are usually ascii, so those are skipped byte-wise without decoding characters
+    let delimiter_len = string
+        .bytes()
+        .take_while(|&byte| byte.is_ascii() && !table.is_syllable_char(byte as char))
+        .count();
+    let string = &string[delimiter_len..];
+
+    // fall back to decoding characters if a non-ascii character is found
+    match string.as_bytes().first() {
+        Some(byte) if !byte.is_ascii() => string
+            .find(|char| table.is_syllable_char(char))
             .map(|index| string.split_at(index))
             .map(|(_, next)| next)
-            .unwrap_or("");
+            .unwrap_or(""), 
+        _ => string, 
     }
+}
 
-    // compute the number of bytes constituting the payload vs checksum
-    let payload_len = buffer
-        .len()
-        .checked_sub(checksum.len())
-        .ok_or(InvalidData::TooShort)?;
+/// Decodes and hashes raw bytes fed to it one-by-one, and verifies the checksum at the end, without storing
+/// the payload. 
+/// 
+/// Since it isn't known which bytes are part of the checksum until the end, the last [`Checksum::len`] bytes
+/// are held back in a window, and are decoded as payload bytes only once pushed out of it. With an
+/// [automatic checksum](Checksum::Auto), the window may also end up holding the last few payload bytes. 
+/// With [chunked checksums](DecodeSettings::checksum_chunk), the checksums of all but the last chunk are
+/// instead verified as they're pushed out of the window. 
+#[derive(Clone, Debug)]
+struct Verifier {
+    /// The most recent raw bytes, used as a ring buffer. 
+    window: [u8; 8], 
+    /// Total number of bytes pushed. 
+    count: usize, 
+    /// Hash of the payload bytes of the current chunk so far. 
+    hash: Fnv1a, 
+    /// The checksum bytes expected for the current chunk, once all of its payload bytes are hashed. 
+    expected: [u8; 8], 
+    /// The bits differing between the checksum bytes of the current chunk so far and those expected. 
+    diff: u8, 
+    /// Index of the first chunk whose checksum didn't match, if any. 
+    corrupt: Option<usize>, 
+    /// The settings used. 
+    settings: DecodeSettings, 
+}
+
+impl Verifier {
+    /// Creates a verifier using given [decoding settings](DecodeSettings). 
+    fn new(settings: DecodeSettings) -> Verifier {
+        Verifier {
+            window: [0; 8], 
+            count: 0, 
+            hash: Fnv1a::new_seeded(settings.checksum_seed), 
+            expected: [0; 8], 
+            diff: 0, 
+            corrupt: None, 
+            settings, 
+        }
+    }
+
+    /// Pushes a raw byte. Returns the decoded payload byte pushed out of the window, if any. 
+    fn push(&mut self, raw: u8) -> Option<u8> {
+        let window_len = self.settings.checksum.len();
+        let evicted = match window_len {
+            0 => Some(raw), 
+            _ => {
+                let slot = &mut self.window[self.count % window_len];
+                let evicted = (self.count >= window_len).then_some(*slot);
+                *slot = raw;
+                evicted
+            }
+        };
+        self.count += 1;
+
+        // a byte followed by a whole window can't be part of the last checksum, so it's either payload or
+        // part of the checksum of a full chunk
+        let chunk_len = match self.settings.checksum_chunk {
+            0 => usize::MAX, 
+            chunk_len => chunk_len, 
+        };
+        evicted.and_then(|byte| self.consume(self.count - 1 - window_len, byte, chunk_len))
+    }
+
+    /// Verifies the checksum remaining in the window, and returns the error of the first chunk whose checksum
+    /// didn't match, if any. Payload bytes still held back in the window are decoded and passed to `out`. 
+    fn finish(mut self, mut out: impl FnMut(u8)) -> Result<()> {
+        let window_len = self.settings.checksum.len();
+        let payload_len = payload_len(self.count, self.settings)?;
+
+        // the oldest byte of the window is at the position the next byte would be written, once it's full
+        let held = self.count.min(window_len);
+        let start = match self.count < window_len {
+            true => 0, 
+            false => self.count.checked_rem(window_len).unwrap_or(0), 
+        };
+        let bytes = self.window;
+        let mut window = bytes[start..held]
+            .iter()
+            .chain(&bytes[..start])
+            .copied();
+
+        // now that the length of the payload is known, so is the length of the last chunk
+        for position in self.count - held..self.count {
+            let byte = window.next().unwrap_or_default();
+            let chunk_len = match self.settings.checksum_chunk {
+                0 => payload_len, 
+                chunk_len => chunk_len.min(payload_len - position / self.frame_len() * chunk_len), 
+            };
+            if let Some(byte) = self.consume(position, byte, chunk_len) {
+                out(byte);
+            }
+        }
+        match self.corrupt {
+            Some(chunk) => Err(checksum_error(chunk, self.settings)), 
+            None => Ok(()), 
+        }
+    }
+
+    /// Decodes and hashes the raw byte at given position if it's a payload byte, or compares it with the
+    /// expected checksum otherwise, given the number of payload bytes in its chunk. Returns the decoded
+    /// payload byte, if any. 
+    fn consume(&mut self, position: usize, byte: u8, chunk_len: usize) -> Option<u8> {
+        let settings = self.settings;
+        let (chunk, offset) = (position / self.frame_len(), position % self.frame_len());
+
+        if offset < chunk_len {
+            let index = chunk * settings.checksum_chunk + offset;
+            let mut byte = byte;
+            restore(core::slice::from_mut(&mut byte), index, settings);
+            self.hash.update(byte);
+            return Some(byte)
+        }
+        // the first checksum byte of a chunk ends its payload, so the next chunk gets a new hash
+        let checksum_index = offset - chunk_len;
 
-    // decode the payload bytes and compute their hash
-    let mut hash = Fnv1a::new();
+        if checksum_index == 0 {
+            let seed = settings.checksum_seed.wrapping_add(chunk as u32).wrapping_add(1);
+            let hash = core::mem::replace(&mut self.hash, Fnv1a::new_seeded(seed));
+            self.expected = hash.bytes(settings.checksum, settings.checksum_order);
+            self.diff = 0;
+        }
+        // accumulate the differing bits rather than exiting early, as with constant time comparison
+        self.diff |= byte ^ self.expected[checksum_index];
 
-    for (i, byte) in buffer.iter_mut().enumerate().take(payload_len) {
-        *byte = running_code(*byte, i);
-        hash.update(*byte);
+        if checksum_index + 1 == settings.checksum.len_for(chunk_len) && self.diff != 0 {
+            self.corrupt.get_or_insert(chunk);
+        }
+        None
     }
 
-    // remove checksum from the end and check whether it matches hash
-    let checksum_match = buffer
-        .drain(payload_len..)
-        .zip(hash.bytes())
-        .all(|(a, b)| a == b);
+    /// Returns the number of bytes of a full chunk including its checksum. 
+    fn frame_len(&self) -> usize {
+        frame_len(self.settings)
+    }
+}
+
+/// Decodes the raw bytes of a [parsed](parse) string in-place, verifies the checksum, and removes it from
+/// the end, leaving only the payload bytes. 
+fn unpack(buffer: &mut Vec<u8>, settings: DecodeSettings) -> Result<()> {
+    let (payload_len, corrupt) = unpack_payload(buffer, settings)?;
+
+    // remove the checksums from the end
+    buffer.truncate(payload_len);
+
+    match corrupt {
+        Some(chunk) => Err(checksum_error(chunk, settings)), 
+        None => Ok(()), 
+    }
+}
+
+/// Checks whether checksum bytes match the hash of the payload, comparing all bytes should
+/// [constant time](DecodeSettings::constant_time) be enabled. 
+fn checksum_matches(checksum: impl Iterator<Item = u8>, hash: Fnv1a, settings: DecodeSettings) -> bool {
+    let mut pairs = checksum.zip(hash.bytes(settings.checksum, settings.checksum_order));
+
+    match settings.constant_time {
+        // accumulate the differing bits, hidden from the optimiser so that it can't exit early
+        true => pairs.fold(0, |diff, (a, b)| core::hint::black_box(diff | (a ^ b))) == 0, 
+        false => pairs.all(|(a, b)| a == b), 
+    }
+}
+
+/// Returns the error for a checksum that didn't match in given chunk: [`InvalidData::ChunkChecksum`] with
+/// [chunked checksums](DecodeSettings::checksum_chunk), and [`InvalidData::Checksum`] otherwise. 
+fn checksum_error(chunk: usize, settings: DecodeSettings) -> InvalidData {
+    match settings.checksum_chunk {
+        0 => InvalidData::Checksum, 
+        _ => InvalidData::ChunkChecksum { chunk }, 
+    }
+}
+
+/// Returns the number of bytes of a full [chunk](DecodeSettings::checksum_chunk) including its checksum, or
+/// [`usize::MAX`] if checksums aren't chunked. 
+fn frame_len(settings: DecodeSettings) -> usize {
+    match settings.checksum_chunk {
+        0 => usize::MAX, 
+        chunk_len => chunk_len.saturating_add(settings.checksum.len_for(chunk_len)), 
+    }
+}
+
+/// Computes the number of payload bytes of a [parsed](parse) string of given length in bytes. 
+/// 
+/// Returns [`InvalidData::TooShort`] if there aren't enough bytes for even an empty payload, and a checksum
+/// error if no payload length yields that many bytes, e.g. with an [automatic checksum](Checksum::Auto). 
+fn payload_len(len: usize, settings: DecodeSettings) -> Result<usize> {
+    let checksum = settings.checksum;
+
+    match checksum.chunked_payload_len(len, settings.checksum_chunk) {
+        Some(payload_len) => Ok(payload_len), 
+        None if len < checksum.len_for(0) => Err(InvalidData::TooShort), 
+        None => Err(checksum_error(len / frame_len(settings), settings)), 
+    }
+}
+
+/// Undoes the [whitening](DecodeSettings::whitening) and [smoothing](DecodeSettings::smoothing) of payload
+/// bytes in-place, should they be enabled, the first of which has index `start`. 
+fn restore(bytes: &mut [u8], start: usize, settings: DecodeSettings) {
+    if let Some(key) = settings.whitening {
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = unwhiten(*byte, start + i, key);
+        }
+    }
+    if settings.smoothing {
+        running_code_slice(bytes, start, settings.seed);
+    }
+}
+
+/// Decodes the payload bytes of a [parsed](parse) string in-place and verifies the checksum of each
+/// [chunk](DecodeSettings::checksum_chunk). The payload bytes are moved to the start of the buffer, followed
+/// by the checksum bytes of all chunks. 
+/// 
+/// Returns `(payload_len, corrupt)`, where `corrupt` is the index of the first chunk whose checksum didn't
+/// match, if any. 
+fn unpack_payload(buffer: &mut Vec<u8>, settings: DecodeSettings) -> Result<(usize, Option<usize>)> {
+    // compute the number of bytes constituting the payload vs checksum
+    let payload_len = payload_len(buffer.len(), settings)?;
+    let chunk_len = match settings.checksum_chunk {
+        0 => usize::MAX, 
+        chunk_len => chunk_len, 
+    };
+    let mut checksums = Vec::new();
+    let mut corrupt = None;
+    let (mut read, mut written, mut chunk) = (0, 0, 0);
+
+    // decode the payload bytes of each chunk and compare their hash with its checksum
+    while read < buffer.len() {
+        let len = chunk_len.min(payload_len - written);
+        let checksum_len = settings.checksum.len_for(len);
+        let payload = &mut buffer[read..read + len];
+
+        restore(payload, written, settings);
+        let mut hash = Fnv1a::new_seeded(settings.checksum_seed.wrapping_add(chunk as u32));
+
+        for &byte in &*payload {
+            hash.update(byte);
+        }
+        let checksum = &buffer[read + len..read + len + checksum_len];
 
-    // if so, return the fully decoded payload bytes
-    checksum_match
-        .then_some(buffer)
-        .ok_or(InvalidData::Checksum)
+        if !checksum_matches(checksum.iter().copied(), hash, settings) {
+            corrupt = corrupt.or(Some(chunk));
+        }
+        // without chunks, the payload and checksum are already in place
+        if settings.checksum_chunk != 0 {
+            checksums.extend_from_slice(checksum);
+            buffer.copy_within(read..read + len, written);
+        }
+        read += len + checksum_len;
+        written += len;
+        chunk += 1;
+    }
+    if settings.checksum_chunk != 0 {
+        buffer.truncate(payload_len);
+        buffer.append(&mut checksums);
+    }
+    Ok((payload_len, corrupt))
 }
 
 #[cfg(test)]
@@ -127,6 +2295,7 @@ mod tests {
         test("uuuuuuuuuuu");
         test("u  u  u  u  u  u  u  u  u  u  u  ");
         test("sive123sive@tive  😀😀😀😀 son👀");
+        test("  👀sive, tive. ");
     }
 
     #[test]
@@ -135,12 +2304,44 @@ mod tests {
             let result = decode_with_settings(input, Checksum::Disabled);
             assert_eq!(result, Err(InvalidData::Syllable));
         };
-        test("😀");
         test("b");
         test("siv");
         test("faevlesa");
     }
 
+    #[test]
+    fn no_syllables_err() {
+        let test = |input, checksum| {
+            let result = decode_with_settings(input, checksum);
+            assert_eq!(result, Err(InvalidData::NoSyllables));
+            assert_eq!(decode_lenient(input, checksum).map(|_| ()), Err(InvalidData::NoSyllables));
+        };
+        test("😀",      Checksum::Disabled);
+        test("123 456", Checksum::Disabled);
+        test(" ,. ",    Checksum::Length1);
+        assert_eq!(decode_with_settings("", Checksum::Disabled), Ok(vec![]));
+    }
+
+    #[test]
+    fn too_long_err() {
+        let data = b"it's such a beautiful day";
+
+        for checksum in [Checksum::Disabled, Checksum::Length1, Checksum::Length4] {
+            let encoded = encode_with_settings(data, Settings {
+                checksum, 
+                ..Default::default()
+            });
+            assert_eq!(decode_bounded(&encoded, checksum, data.len()).as_deref(), Ok(&data[..]));
+            assert_eq!(decode_bounded(&encoded, checksum, usize::MAX).as_deref(), Ok(&data[..]));
+            assert_eq!(decode_bounded(&encoded, checksum, data.len() - 1), Err(InvalidData::TooLong {
+                max: data.len() - 1, 
+            }));
+        }
+        // the bound is checked before the rest of the string is parsed
+        let result = decode_bounded("sive tive faevlesa", Checksum::Disabled, 1);
+        assert_eq!(result, Err(InvalidData::TooLong { max: 1 }));
+    }
+
     #[test]
     fn too_short_err() {
         let test = |input, checksum| {
@@ -151,4 +2352,587 @@ mod tests {
         test("sive", Checksum::Length2);
         test("uu",   Checksum::Length3);
     }
+
+    #[test]
+    fn unexpected_case_err() {
+        let settings = DecodeSettings {
+            checksum: Checksum::Disabled, 
+            strict_case: true, 
+            ..Default::default()
+        };
+        let test = |input: &str, offset| {
+            let expected = Err(InvalidData::UnexpectedCase { offset });
+            assert_eq!(decode_with_settings(input, settings).map(drop), expected);
+            assert_eq!(verify(input, settings), expected);
+
+            for fragment_len in 1..input.len() {
+                let mut decoder = Decoder::new(settings);
+                let result = input
+                    .as_bytes()
+                    .chunks(fragment_len)
+                    .try_for_each(|fragment| decoder.push(core::str::from_utf8(fragment).unwrap()));
+                assert_eq!(result.and_then(|_| decoder.finish().map(drop)), expected);
+            }
+        };
+        test("siVe", 2);
+        test("Sive tiVe", 7);
+        test("sive, TIVE", 7);
+
+        let decorated = encode_with_settings(b"it's such a beautiful day", Settings {
+            decorate: true, 
+            ..Default::default()
+        });
+        let settings = DecodeSettings {
+            strict_case: true, 
+            ..Default::default()
+        };
+        let decoded = decode_with_settings(&decorated, settings);
+        assert_eq!(decoded.as_deref(), Ok(&b"it's such a beautiful day"[..]));
+    }
+
+    #[test]
+    fn normalized() {
+        let encoded = encode(b"it's such a beautiful day");
+        let transcribed: String = encoded
+            .chars()
+            .map(|char| match char {
+                'o' => '0', 
+                'e' => 'É', 
+                'u' => 'ü', 
+                char => char, 
+            })
+            .collect();
+        assert_ne!(decode(&transcribed).as_deref(), Ok(&b"it's such a beautiful day"[..]));
+
+        let decoded = decode_normalized(&transcribed, Checksum::default(), LOOKALIKES);
+        assert_eq!(decoded.as_deref(), Ok(&b"it's such a beautiful day"[..]));
+
+        // mappings are configurable
+        let decoded = decode_normalized("7", Checksum::Disabled, &[('7', 'u')]);
+        assert_eq!(decoded, decode_with_settings("u", Checksum::Disabled));
+    }
+
+    #[test]
+    fn payload_diff() {
+        let a = encode([1, 2, 3, 4]);
+        let b = encode([1, 0, 3, 0, 5]);
+        assert_eq!(super::payload_diff(&a, &a, Checksum::default()), Ok(0));
+        assert_eq!(super::payload_diff(&a, &b, Checksum::default()), Ok(3));
+        assert_eq!(super::payload_diff(&a, "faevlesa", Checksum::default()), Err(InvalidData::Syllable));
+        assert_eq!(super::payload_diff("", &b, Checksum::default()), Err(InvalidData::TooShort));
+    }
+
+    #[test]
+    fn prefix() {
+        let data = b"it's such a beautiful day";
+
+        for checksum in [Checksum::Disabled, Checksum::Length1, Checksum::Length4] {
+            let settings = Settings {
+                checksum, 
+                ..Default::default()
+            };
+            let encoded = encode_with_settings(data, settings);
+
+            for max_bytes in 0..data.len() + 6 {
+                let prefix = &data[..max_bytes.min(data.len())];
+                assert_eq!(decode_prefix(&encoded, checksum, max_bytes).as_deref(), Ok(prefix));
+            }
+        }
+        // only the prefix is parsed
+        assert_eq!(decode_prefix("sive faevlesa", Checksum::Length1, 0), Ok(vec![]));
+        assert_eq!(decode_prefix("sive", Checksum::Length2, 1), Err(InvalidData::TooShort));
+    }
+
+    #[test]
+    fn chars() {
+        let data = b"it's such a beautiful day";
+        let encoded = encode_with_settings(data, Settings {
+            decorate: true, 
+            ..Default::default()
+        });
+        assert_eq!(decode_chars(encoded.chars(), Checksum::default()).as_deref(), Ok(&data[..]));
+        assert_eq!(decode_chars("  sive, 😀 tive".chars(), Checksum::Disabled), decode_with_settings(
+            "  sive, 😀 tive", 
+            Checksum::Disabled, 
+        ));
+        assert_eq!(decode_chars("😀".chars(), Checksum::Disabled), Err(InvalidData::NoSyllables));
+
+        // decoding stops at the first unrecognized syllable
+        let mut chars = "sive faevlesa tive".chars();
+        assert_eq!(decode_chars(&mut chars, Checksum::Disabled), Err(InvalidData::Syllable));
+        assert_eq!(chars.as_str(), "tive");
+    }
+
+    #[test]
+    fn empty_err() {
+        let test = |input, checksum| {
+            let result = decode_nonempty(input, checksum);
+            assert_eq!(result, Err(InvalidData::Empty));
+        };
+        test("",     Checksum::Disabled);
+        test("",     Checksum::Length1);
+        test(&encode_with_settings([], Settings {
+            checksum: Checksum::Length4, 
+            ..Default::default()
+        }), Checksum::Length4);
+
+        assert_eq!(decode_nonempty("sive", Checksum::Length2), Err(InvalidData::TooShort));
+        assert_eq!(decode_nonempty("😀", Checksum::Disabled), Err(InvalidData::NoSyllables));
+    }
+
+    #[test]
+    fn streaming() {
+        let encoded = encode_with_settings(b"it's such a beautiful day", Settings {
+            decorate: true, 
+            ..Default::default()
+        });
+
+        for fragment_len in 1..encoded.len() {
+            let mut decoder = Decoder::new(Checksum::default());
+
+            for fragment in encoded.as_bytes().chunks(fragment_len) {
+                decoder.push(std::str::from_utf8(fragment).unwrap()).unwrap();
+            }
+            assert_eq!(decoder.finish().as_deref(), Ok(&b"it's such a beautiful day"[..]));
+        }
+    }
+
+    #[test]
+    fn into_cleared_on_err() {
+        let mut buffer = vec![1, 2, 3];
+        let result = decode_into("sive faevlesa", Checksum::Disabled, &mut buffer);
+        assert_eq!(result, Err(InvalidData::Syllable));
+        assert!(buffer.is_empty());
+
+        let mut buffer = vec![1, 2, 3];
+        let result = decode_into("sive sive", Checksum::Length1, &mut buffer);
+        assert_eq!(result, Err(InvalidData::Checksum));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn multi() {
+        let items: [&[u8]; 3] = [b"it's such", b"a beautiful", b"day"];
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            decorate: true, 
+            ..Default::default()
+        };
+        for separator in ['|', ';', '/', '\n'] {
+            let joined = items
+                .map(|item| encode_with_settings(item, settings))
+                .join(separator.encode_utf8(&mut [0; 4]));
+            let decoded = decode_multi(&joined, settings, separator);
+            assert_eq!(decoded, items.map(|item| Ok(item.to_vec())));
+        }
+        // empty segments are decoded like empty strings
+        let joined = format!("|{}||", encode(b"sun"));
+        let decoded = decode_multi(joined, Checksum::Length1, '|');
+        let empty = || Err(InvalidData::TooShort);
+        assert_eq!(decoded, [empty(), Ok(b"sun".to_vec()), empty(), empty()]);
+        assert_eq!(decode_multi("", Checksum::Disabled, '|'), [Ok(vec![])]);
+    }
+
+    #[test]
+    fn all_interpretations() {
+        let data = b"it's such a beautiful day";
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(data, settings);
+        assert_eq!(decode_all(&encoded, settings), [data.to_vec()]);
+        assert!(decode_all(encoded.replace(' ', ""), settings).contains(&data.to_vec()));
+
+        // without a checksum, every segmentation is valid
+        let decode = |string| decode_with_settings(string, Checksum::Disabled).unwrap();
+        let decoded = decode_all("ous", Checksum::Disabled);
+        assert!(decoded.contains(&decode("ous")));
+        assert!(decoded.contains(&decode("o us")));
+        assert_eq!(decode_all("o us", Checksum::Disabled), [decode("o us")]);
+
+        assert_eq!(decode_all("b", Checksum::Disabled), Vec::<Vec<u8>>::new());
+        assert_eq!(decode_all("", Checksum::Disabled), [vec![]]);
+
+        // the search is bounded
+        let decoded = decode_all("ous".repeat(100), Checksum::Disabled);
+        assert!(!decoded.is_empty() && decoded.len() <= super::MAX_MATCHES);
+    }
+
+    #[test]
+    fn ignorable() {
+        let data = b"it's such a beautiful day";
+        let encoded = encode_with_settings(data, Settings {
+            decorate: true, 
+            ..Default::default()
+        });
+
+        // insert a mark after every letter, so that it lands within syllables
+        for mark in ['\u{301}', '\u{345}', '\u{200D}', '\u{AD}', '\u{FEFF}'] {
+            let marked: String = encoded
+                .chars()
+                .flat_map(|char| [Some(char), char.is_alphabetic().then_some(mark)])
+                .flatten()
+                .collect();
+            let settings = DecodeSettings {
+                strict_case: true, 
+                ..Default::default()
+            };
+            assert_eq!(decode_with_settings(&marked, settings).as_deref(), Ok(&data[..]), "{marked:?}");
+            assert_eq!(decode_chars(marked.chars(), settings).as_deref(), Ok(&data[..]), "{marked:?}");
+            assert_eq!(verify(&marked, settings), Ok(()));
+        }
+        // a mark splitting "ous" would otherwise yield "o" and "us"
+        assert_eq!(decode_with_settings("o\u{301}us", Checksum::Disabled), Ok(vec![64]));
+        assert_eq!(decode_with_settings("o\u{301}", Checksum::Disabled), Ok(vec![182]));
+        assert_eq!(decode_with_settings("\u{301}", Checksum::Disabled), Err(InvalidData::NoSyllables));
+        assert_eq!(decode_with_settings("óus", Checksum::Disabled), Err(InvalidData::Syllable));
+
+        // the lenient decoder agrees, making no corrections
+        let lenient = |string| decode_lenient(string, Checksum::Disabled);
+        assert_eq!(lenient("o\u{301}us"), Ok((vec![64], vec![])));
+        assert_eq!(lenient("o\u{301}"), Ok((vec![182], vec![])));
+        assert_eq!(lenient("\u{301}").map(|_| ()), Err(InvalidData::NoSyllables));
+        for mark in ['\u{301}', '\u{200D}'] {
+            let marked: String = encoded
+                .chars()
+                .flat_map(|char| [Some(char), char.is_alphabetic().then_some(mark)])
+                .flatten()
+                .collect();
+            let decoded = decode_lenient(&marked, Settings { decorate: true, ..Default::default() });
+            assert_eq!(decoded, Ok((data.to_vec(), vec![])), "{marked:?}");
+        }
+
+        // digits are delimiters in the standard alphabet, whether or not a mark follows them
+        let encoded = encode_with_settings(data, Settings::default());
+        for delimiter in ["1\u{301}", "\u{301}1", "1\u{301}2"] {
+            let marked = encoded.replace(' ', delimiter);
+            assert_eq!(decode(&marked).as_deref(), Ok(&data[..]), "{marked:?}");
+        }
+        // marks within syllables longer than the built-in ones
+        let syllables: [String; 256] =
+            core::array::from_fn(|i| format!("syllabification{}", syllable(i as u8)));
+        let table = Table::from_syllables(core::array::from_fn(|i| syllables[i].as_str())).unwrap();
+        let encoded = encode_with_table(data, &table, Settings::default());
+        let marked: String = encoded
+            .chars()
+            .flat_map(|char| [Some(char), char.is_alphabetic().then_some('\u{301}')])
+            .flatten()
+            .collect();
+        assert_eq!(decode_with_table(&marked, &table, Checksum::default()).as_deref(), Ok(&data[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reader_err() {
+        let result = decode_from_reader(&[0xFF, 0xFE][..], Checksum::Disabled);
+        assert!(matches!(result, Err(DecodeError::Io(_))));
+
+        let result = decode_from_reader(&b"faevlesa"[..], Checksum::Disabled);
+        assert!(matches!(result, Err(DecodeError::Data(InvalidData::Syllable))));
+    }
+
+    /// Reader yielding data in chunks of a given size. 
+    #[cfg(feature = "std")]
+    struct Chunked<'a>(&'a [u8], usize);
+
+    #[cfg(feature = "std")]
+    impl std::io::Read for Chunked<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.1.min(buffer.len()).min(self.0.len());
+            buffer[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn verify_reader() {
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            decorate: true, 
+            pad_syllables: Some('·'), 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(b"it's such a beautiful day", settings);
+        let mismatched = encode_with_settings(b"it's such a beautiful day", Settings {
+            checksum_seed: 1, 
+            ..settings
+        });
+        let strings = [&encoded, &mismatched, "", "  ", "!!!", "faevlesa", "sive sive", "😀 sive"];
+
+        for string in strings {
+            let expected = match verify(string, settings) {
+                Ok(()) => Ok(true), 
+                Err(InvalidData::Checksum) => Ok(false), 
+                Err(error) => Err(error), 
+            };
+            for chunk_len in [1, 2, 3, 7, 4096] {
+                let result = super::verify_reader(Chunked(string.as_bytes(), chunk_len), settings);
+                let result = result.map_err(|error| match error {
+                    DecodeError::Data(error) => error, 
+                    DecodeError::Io(error) => panic!("{error}"), 
+                });
+                assert_eq!(result, expected, "{string:?}, {chunk_len}");
+            }
+        }
+        assert_eq!(super::verify_reader(mismatched.as_bytes(), settings).ok(), Some(false));
+
+        let result = super::verify_reader(Chunked(&[b's', b'i', 0xFF], 1), settings);
+        assert!(matches!(result, Err(DecodeError::Io(_))));
+    }
+
+    #[test]
+    fn structure() {
+        let raw = DecodeSettings {
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        let data: Vec<u8> = (0..=255).rev().collect();
+        let decorated = Settings {
+            decorate: true, 
+            ..Default::default()
+        };
+        let strings = [
+            encode(&data), 
+            encode_with_settings(&data, decorated), 
+            encode_with_settings(&data, Settings::robust()), 
+            "  sive\u{AD}sive, \u{AD}sive\u{AD} ".to_owned(), 
+            String::new(), 
+        ];
+        for string in strings {
+            let structure = parse_structure(&string);
+            let mut end = structure.leading.end;
+            let mut indices = Vec::new();
+
+            // the spans are contiguous and cover the whole string
+            for word in &structure.words {
+                assert_eq!(word.span.start, end);
+                assert_eq!(word.span.end, word.delimiter.start);
+                end = word.delimiter.end;
+
+                let mut syllable_end = word.span.start;
+
+                for syllable in &word.syllables {
+                    assert_eq!(syllable.span.start, syllable_end);
+                    syllable_end = syllable.span.end;
+                    indices.push(syllable.index.unwrap());
+                }
+                assert_eq!(syllable_end, word.span.end);
+            }
+            assert_eq!(end, string.len());
+            // unlike the statistics, ignorable characters within words don't split them
+            if string.is_ascii() {
+                let stats = decode_stats(&string, raw).map_or(0, |stats| stats.word_count);
+                assert_eq!(structure.words.len(), stats);
+            }
+            assert_eq!(indices, decode_with_settings(&string, raw).unwrap_or_default());
+        }
+        let structure = parse_structure(" sive faevlesa.");
+        assert_eq!(structure.leading, 0..1);
+        assert_eq!(structure.words[1].span, 6..14);
+        assert_eq!(structure.words[1].delimiter, 14..15);
+
+        let unrecognized = structure.words[1].syllables.last().unwrap();
+        assert_eq!(unrecognized.index, None);
+        assert_eq!(unrecognized.span.end, 14);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn decode_reader() {
+        use std::io::{self, Read};
+
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let settings = [
+            Settings::default(), 
+            Settings {
+                checksum: Checksum::Auto, 
+                decorate: true, 
+                pad_syllables: Some('·'), 
+                ..Default::default()
+            }, 
+            Settings {
+                checksum: Checksum::Length3, 
+                checksum_chunk: 10, 
+                ..Settings::robust()
+            }, 
+        ];
+        for settings in settings {
+            let encoded = encode_with_settings(&data, settings);
+            let mismatched = encode_with_settings(&data[..20], Settings {
+                checksum_seed: 1, 
+                ..settings
+            });
+            let short = encode_with_settings(&data[..20], settings);
+            let invalid = ["", "  ", "!!!", "faevlesa", "sive sive", "😀 sive"];
+            let strings = [&encoded, &mismatched, &short].into_iter().map(String::as_str).chain(invalid);
+
+            for string in strings {
+                let expected = decode_with_settings(string, settings);
+
+                for chunk_len in [1, 2, 3, 7, 4096] {
+                    for read_len in [1, 5, 4096] {
+                        let mut reader = DecodeReader::new(Chunked(string.as_bytes(), chunk_len), settings);
+                        let mut decoded = Vec::new();
+                        let mut buffer = vec![0; read_len];
+
+                        let result = loop {
+                            match reader.read(&mut buffer) {
+                                Ok(0) => break Ok(decoded), 
+                                Ok(len) => decoded.extend_from_slice(&buffer[..len]), 
+                                Err(error) => {
+                                    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+                                    let error = error.into_inner().unwrap().downcast::<InvalidData>();
+                                    break Err(*error.unwrap())
+                                }
+                            }
+                        };
+                        assert_eq!(result, expected, "{string:?}, {chunk_len}, {read_len}");
+                        assert_eq!(reader.read(&mut buffer).unwrap(), 0);
+                    }
+                }
+            }
+            let mut decoded = Vec::new();
+            io::copy(&mut DecodeReader::new(encoded.as_bytes(), settings), &mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+        let mut reader = DecodeReader::new(Chunked(&[b's', b'i', 0xFF], 1), Checksum::Disabled);
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.get_ref().unwrap().downcast_ref::<InvalidData>().is_none());
+
+        // payload bytes held back in the window by an automatic checksum shorter than it are decoded too
+        let settings = Settings {
+            checksum: Checksum::Auto, 
+            ..Default::default()
+        };
+        assert_eq!(decode_array(encode_with_settings([7; 16], settings), settings), Ok([7; 16]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reader_err_source() {
+        use std::{error::Error, io};
+
+        let error = decode_from_reader(&b"faevlesa"[..], Checksum::Disabled).unwrap_err();
+        let source = error.source().and_then(|source| source.downcast_ref::<InvalidData>());
+        assert_eq!(source, Some(&InvalidData::Syllable));
+
+        let error = decode_from_reader(&[0xFF, 0xFE][..], Checksum::Disabled).unwrap_err();
+        let source = error.source().and_then(|source| source.downcast_ref::<io::Error>());
+        assert_eq!(source.map(io::Error::kind), Some(io::ErrorKind::InvalidData));
+
+        // both kinds of errors convert with `?`
+        let read = || -> std::result::Result<Vec<u8>, DecodeError> {
+            let mut string = String::new();
+            io::Read::read_to_string(&mut &b"faevlesa"[..], &mut string)?;
+            Ok(decode(string)?)
+        };
+        assert!(matches!(read(), Err(DecodeError::Data(InvalidData::Syllable))));
+    }
+
+    #[test]
+    fn autodetect() {
+        let data = b"it's such a beautiful day";
+
+        for checksum in [Checksum::Length1, Checksum::Length2, Checksum::Length4, Checksum::Length8] {
+            let encoded = encode_with_settings(data, Settings {
+                checksum, 
+                ..Default::default()
+            });
+            assert_eq!(decode_autodetect(encoded), Ok((data.to_vec(), checksum)));
+        }
+        // auto is reported as the length it picks
+        for len in [1, 20, 40, 100] {
+            let data = vec![7; len];
+            let encoded = encode_with_settings(&data, Settings {
+                checksum: Checksum::Auto, 
+                ..Default::default()
+            });
+            let checksum = Checksum::from_len(Checksum::Auto.len_for(len)).unwrap();
+            assert_eq!(decode_autodetect(encoded), Ok((data, checksum)));
+        }
+        assert_eq!(decode_autodetect(""), Err(InvalidData::TooShort));
+    }
+
+    #[test]
+    fn stats() {
+        let data = b"it's such a beautiful day";
+
+        for decorate in [false, true] {
+            let settings = Settings {
+                decorate, 
+                ..Default::default()
+            };
+            let encoded = encode_with_settings(data, settings);
+            let stats = decode_stats(&encoded, settings).unwrap();
+            assert_eq!(stats.payload_len, data.len());
+            assert_eq!(stats.syllable_count, data.len() + 1);
+            assert_eq!(stats.word_count, encoded.split(' ').count());
+            assert_eq!(stats.decorated, decorate);
+        }
+        let stats = decode_stats("  ~sive---tive~  ", Checksum::Disabled).unwrap();
+        assert_eq!((stats.word_count, stats.skipped_chars, stats.decorated), (2, 9, false));
+        assert_eq!(decode_stats("SIVE TIVE", Checksum::Disabled).map(|stats| stats.decorated), Ok(false));
+        assert_eq!(decode_stats("sive", Checksum::Length2), Err(InvalidData::TooShort));
+    }
+
+    #[test]
+    fn verbose_mismatch() {
+        let output = decode_verbose("sive sive", Checksum::Length1).unwrap();
+        assert_eq!(output.payload.len(), 1);
+        assert_eq!(output.checksum_bytes.len(), 1);
+        assert!(!output.checksum_ok);
+    }
+
+    #[test]
+    fn verify_matches_decode() {
+        let inputs = ["", "u", "sive", "sive sive", "uuuuuuuuuuu", "faevlesa", "Atemorni telphocom. Neideu."];
+        let checksums = [
+            Checksum::Disabled, 
+            Checksum::Length1, 
+            Checksum::Length3, 
+            Checksum::Length8, 
+        ];
+
+        for input in inputs {
+            for checksum in checksums {
+                let expected = decode_with_settings(input, checksum).map(|_| ());
+                assert_eq!(verify(input, checksum), expected, "{input}, {checksum:?}");
+            }
+        }
+        let data = b"it's such a beautiful day";
+
+        for checksum in checksums {
+            let encoded = encode_with_settings(data, Settings {
+                checksum, 
+                ..Default::default()
+            });
+            assert_eq!(verify(encoded, checksum), Ok(()));
+        }
+    }
+
+    #[test]
+    fn constant_time() {
+        let encoded = encode_with_settings(b"it's such a beautiful day", Settings {
+            checksum: Checksum::Length8, 
+            ..Default::default()
+        });
+
+        for input in [&encoded, "sive sive", "sive"] {
+            for checksum in [Checksum::Disabled, Checksum::Length1, Checksum::Length8] {
+                let settings = DecodeSettings {
+                    checksum, 
+                    constant_time: true, 
+                    ..Default::default()
+                };
+                let expected = decode_with_settings(input, checksum);
+                assert_eq!(decode_with_settings(input, settings), expected, "{input}, {checksum:?}");
+                assert_eq!(verify(input, settings), expected.map(|_| ()), "{input}, {checksum:?}");
+            }
+        }
+    }
 }
@@ -1,26 +1,608 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{convert::Infallible, fmt, mem};
 use crate::*;
+use crate::syllables::Lookup;
+
+#[cfg(feature = "std")]
+use std::io;
+use thiserror::Error;
 
 /// Settings used when encoding. 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct Settings {
     /// Maximum number of syllables allowed in a word. Note that the actual number of syllables in a word may
-    /// be smaller. Default: `3`. 
+    /// be smaller. `None` and `Some(0)` both disable word-breaks due to length, like
+    /// [`WordGrouping::Fixed(0)`](WordGrouping::Fixed), yielding the longest possible words. Default: `3`. 
     pub word_len: Option<u8>, 
+    /// Minimum number of syllables in a word before it may be broken due to its length. This must not
+    /// exceed [`word_len`](Settings::word_len): [`Settings::validate`] rejects it if it does, and if not
+    /// validated, the minimum wins. Note that word-breaks required to avoid parsing ambiguities are always
+    /// inserted, so the actual number of syllables in a word may still be smaller. Default: `None`. 
+    pub min_word_len: Option<u8>, 
+    /// How syllables are grouped into words. Default: [`WordGrouping::Variable`]. 
+    pub grouping: WordGrouping, 
+    /// Character separating words, e.g. `'-'` for keys printed in groups like `XXXX-XXXX-XXXX`. Periods and
+    /// commas replace it when [decorating](Settings::decorate). Since the decoder skips all characters that
+    /// can't be part of a syllable, the separator must be one of those, and it must not be
+    /// [ignored within words](crate::decode_with_settings) like zero-width spaces and combining marks are;
+    /// see [`Settings::validate`]. Default: `' '`. 
+    pub separator: char, 
+    /// Character padding each syllable to the length of the longest syllables, four letters, so that every
+    /// byte takes up the same width, e.g. for display in a monospace grid. Like the
+    /// [separator](Settings::separator), it must be skipped by the decoder without being ignored within
+    /// words; see [`Settings::validate`]. If `None`, syllables aren't padded. Default: `None`. 
+    pub pad_syllables: Option<char>, 
+    /// If enabled, word-breaks are only inserted between syllables where needed for the decoder to tell them
+    /// apart, rather than wherever the first could be continued by the second, yielding fewer and longer
+    /// words. The decoder then has to backtrack, which makes it accept more mistyped strings, so a
+    /// [checksum](Settings::checksum) is recommended. This setting must match when decoding; see
+    /// [`DecodeSettings`]. Default: `false`. 
+    pub minimal_breaks: bool, 
     /// The checksum settings used. Default: [`Checksum::Length1`]. 
     pub checksum: Checksum, 
+    /// Seed mixed into the checksum. Strings encoded with one seed fail the checksum when decoded with
+    /// another, which can be used to keep encodings from different domains apart. Note that this provides no
+    /// security. This setting must match when decoding; see [`DecodeSettings`]. Default: `0`. 
+    pub checksum_seed: u32, 
+    /// Byte order of the checksum. This only matters for interoperating with other implementations of the
+    /// encoding that order the bytes of the hash differently. This setting must match when decoding; see
+    /// [`DecodeSettings`]. Default: [`Endianness::Little`]. 
+    pub checksum_order: Endianness, 
+    /// Number of payload bytes covered by each checksum. If non-zero, the payload is split into chunks of
+    /// this many bytes, each followed by its own [checksum](Settings::checksum), so that a corrupt chunk can
+    /// be identified when decoding; see [`InvalidData::ChunkChecksum`]. The last chunk may be shorter, and
+    /// an [automatic checksum](Checksum::Auto) is sized by the length of each chunk. The checksum of each
+    /// chunk is seeded with the [checksum seed](Settings::checksum_seed) plus the index of the chunk, so
+    /// that reordered chunks are detected too. `0` places a single checksum after all data. This setting
+    /// must match when decoding; see [`DecodeSettings`]. Default: `0`. 
+    pub checksum_chunk: usize, 
     /// If enabled, encoded strings are decorated with commas, periods, and sentence casing. This can make 
     /// the encoded string more readable, but also longer. All decorations are ignored when decoding.
     /// Default: `false`. 
     pub decorate: bool, 
+    /// If enabled, all letters of the encoded string are uppercase, e.g. for printing on labels. Word breaks
+    /// and [decorations](Settings::decoration) are kept as-is. Since decoding ignores case, this needn't
+    /// match when decoding, unless [strict case](DecodeSettings::strict_case) is enabled, which rejects
+    /// uppercase letters in the middle of words. Default: `false`. 
+    pub uppercase: bool, 
+    /// If enabled, the _apparent_ entropy of the data is increased before encoding, which avoids repeated
+    /// syllables for repetitive data such as `[0, 0, 0, 0]`. Disabling this makes each syllable correspond
+    /// directly to the byte it encodes, which can be useful for debugging or interoperability. This setting
+    /// must match when decoding; see [`DecodeSettings`]. Default: `true`. 
+    pub smoothing: bool, 
+    /// Seed mixed into the [smoothing](Settings::smoothing). Identical data encoded with different seeds
+    /// yields different-looking strings, which can be used to separate encodings from different domains.
+    /// Note that this is purely cosmetic and provides no security. This setting must match when decoding; see
+    /// [`DecodeSettings`]. Default: `0`. 
+    pub seed: u64, 
+    /// Key of an additional whitening pass, or `None` to disable it. If enabled, each payload byte is mapped
+    /// through a permutation of all byte values, chosen by the key and the index of the byte, after
+    /// [smoothing](Settings::smoothing) and before its syllable is looked up. Smoothing repeats every 256
+    /// bytes, so data made of e.g. repeated records still yields visibly repeating strings, and some
+    /// syllables remain overused; the permutations never repeat, at a slight cost in speed. Like smoothing, 
+    /// this is purely cosmetic and provides no security. This changes the encoded string, so this setting
+    /// must match when decoding; see [`DecodeSettings`]. Default: `None`. 
+    pub whitening: Option<u64>, 
+    /// The built-in table of syllables used. This setting must match when decoding; see [`DecodeSettings`].
+    /// Default: [`Alphabet::Standard`]. 
+    pub alphabet: Alphabet, 
+    /// The decorations used when [`decorate`](Settings::decorate) is enabled. Default:
+    /// [`Decoration::default`]. 
+    pub decoration: Decoration, 
 }
 
 impl Default for Settings {
     fn default() -> Self {
+        Settings::DEFAULT
+    }
+}
+
+impl Settings {
+    /// The default settings, for use in `const` contexts. 
+    const DEFAULT: Settings = Settings {
+        word_len: Some(3), 
+        min_word_len: None, 
+        grouping: WordGrouping::Variable, 
+        separator: ' ', 
+        pad_syllables: None, 
+        minimal_breaks: false, 
+        checksum: Checksum::Length1, 
+        checksum_seed: 0, 
+        checksum_order: Endianness::Little, 
+        checksum_chunk: 0, 
+        decorate: false, 
+        decoration: Decoration::DEFAULT, 
+        uppercase: false, 
+        smoothing: true, 
+        seed: 0, 
+        whitening: None, 
+        alphabet: Alphabet::Standard, 
+    };
+
+    /// Settings for the shortest strings: no decoration, no checksum, and words as long as possible. Suits
+    /// strings that are copied rather than read, e.g. in URLs. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Settings;
+    /// 
+    /// let encoded = bunk::encode_with_settings(b"aftersun", Settings::compact());
+    /// 
+    /// assert!(encoded.len() < bunk::encode(b"aftersun").len());
+    /// assert_eq!(bunk::decode_with_settings(encoded, Settings::compact())?, b"aftersun");
+    /// # Ok::<(), bunk::InvalidData>(())
+    /// ```
+    pub const fn compact() -> Settings {
+        Settings {
+            word_len: None, 
+            checksum: Checksum::Disabled, 
+            ..Settings::DEFAULT
+        }
+    }
+
+    /// Settings for strings read by people: decorated as sentences, with short words and a one-byte
+    /// checksum. Suits strings that are read aloud or transcribed by hand. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Settings;
+    /// 
+    /// let encoded = bunk::encode_with_settings(b"it's such a beautiful day", Settings::readable());
+    /// 
+    /// assert!(encoded.ends_with('.'));
+    /// assert_eq!(bunk::decode_with_settings(encoded, Settings::readable())?, b"it's such a beautiful day");
+    /// # Ok::<(), bunk::InvalidData>(())
+    /// ```
+    pub const fn readable() -> Settings {
+        Settings {
+            word_len: Some(2), 
+            checksum: Checksum::Length1, 
+            decorate: true, 
+            ..Settings::DEFAULT
+        }
+    }
+
+    /// Settings for strings that must survive transcription errors: decorated, with a four-byte checksum,
+    /// so that a mistyped string is all but guaranteed to be rejected. Suits e.g. recovery codes. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::{InvalidData, Settings};
+    /// 
+    /// let encoded = bunk::encode_with_settings(b"aftersun", Settings::robust());
+    /// 
+    /// assert_eq!(bunk::decode_with_settings(&encoded, Settings::robust())?, b"aftersun");
+    /// assert_eq!(bunk::decode_with_settings(&encoded[1..], Settings::robust()), Err(InvalidData::Checksum));
+    /// # Ok::<(), bunk::InvalidData>(())
+    /// ```
+    pub const fn robust() -> Settings {
         Settings {
-            word_len: Some(3), 
-            checksum: Checksum::default(), 
-            decorate: false, 
+            checksum: Checksum::Length4, 
+            decorate: true, 
+            ..Settings::DEFAULT
+        }
+    }
+
+    /// Creates a [builder](SettingsBuilder) of settings, starting from the default settings. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::{Checksum, Settings};
+    /// 
+    /// let settings = Settings::builder()
+    ///     .word_len(5)
+    ///     .checksum(Checksum::Length2)
+    ///     .decorate(true)
+    ///     .build()?;
+    /// 
+    /// assert_eq!(settings.word_len, Some(5));
+    /// # Ok::<(), bunk::SettingsError>(())
+    /// ```
+    pub fn builder() -> SettingsBuilder {
+        SettingsBuilder::default()
+    }
+
+    /// Verifies that the settings are sensible. 
+    /// 
+    /// Settings failing this can still be used for encoding, but may produce surprising output; see the
+    /// documentation of the offending fields. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::{Settings, SettingsError};
+    /// 
+    /// assert_eq!(Settings::default().validate(), Ok(()));
+    /// 
+    /// let settings = Settings {
+    ///     word_len: Some(3), 
+    ///     min_word_len: Some(5), 
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(settings.validate(), Err(SettingsError::MinExceedsMax { min: 5, max: 3 }));
+    /// ```
+    pub fn validate(&self) -> core::result::Result<(), SettingsError> {
+        let joins = |char| syllables::is_syllable_char(self.alphabet, char) || syllables::is_ignorable(char);
+        // empty delimiters consist of only ignorable characters too
+        let joins_str = |delimiter: &str| {
+            delimiter.chars().any(|char| syllables::is_syllable_char(self.alphabet, char))
+                || delimiter.chars().all(syllables::is_ignorable)
+        };
+
+        if joins(self.separator) {
+            return Err(SettingsError::Separator { separator: self.separator })
+        }
+        if let Some(padding) = self.pad_syllables {
+            if joins(padding) {
+                return Err(SettingsError::Padding { padding })
+            }
+        }
+        if joins_str(self.decoration.period) {
+            return Err(SettingsError::Period { period: self.decoration.period })
+        }
+        if joins_str(self.decoration.comma) {
+            return Err(SettingsError::Comma { comma: self.decoration.comma })
         }
+        if let Some(delimiter) = self.decoration.checksum {
+            if joins_str(delimiter) {
+                return Err(SettingsError::ChecksumDelimiter { delimiter })
+            }
+        }
+        let Decoration{ mode, period_threshold: period, comma_threshold: comma, .. } = self.decoration;
+
+        if mode == DecorationMode::Hashed && comma > period {
+            return Err(SettingsError::Thresholds { comma, period })
+        }
+        match (self.word_len, self.min_word_len) {
+            (Some(max @ 1..), Some(min)) if min > max => Err(SettingsError::MinExceedsMax { min, max }), 
+            _ => Ok(()), 
+        }
+    }
+}
+
+/// Builder of [settings](Settings), created with [`Settings::builder`]. 
+/// 
+/// Fields not set keep their default values. The settings are [validated](Settings::validate) when built. 
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SettingsBuilder(Settings);
+
+impl SettingsBuilder {
+    /// Sets the [maximum word length](Settings::word_len). 
+    pub fn word_len(mut self, word_len: impl Into<Option<u8>>) -> SettingsBuilder {
+        self.0.word_len = word_len.into();
+        self
+    }
+
+    /// Sets the [minimum word length](Settings::min_word_len). 
+    pub fn min_word_len(mut self, min_word_len: impl Into<Option<u8>>) -> SettingsBuilder {
+        self.0.min_word_len = min_word_len.into();
+        self
+    }
+
+    /// Sets the [word grouping](Settings::grouping). 
+    pub fn grouping(mut self, grouping: WordGrouping) -> SettingsBuilder {
+        self.0.grouping = grouping;
+        self
+    }
+
+    /// Sets the [word separator](Settings::separator). 
+    pub fn separator(mut self, separator: char) -> SettingsBuilder {
+        self.0.separator = separator;
+        self
+    }
+
+    /// Sets the character [padding syllables](Settings::pad_syllables). 
+    pub fn pad_syllables(mut self, pad_syllables: impl Into<Option<char>>) -> SettingsBuilder {
+        self.0.pad_syllables = pad_syllables.into();
+        self
+    }
+
+    /// Sets whether to insert [minimal breaks](Settings::minimal_breaks). 
+    pub fn minimal_breaks(mut self, minimal_breaks: bool) -> SettingsBuilder {
+        self.0.minimal_breaks = minimal_breaks;
+        self
+    }
+
+    /// Sets the [checksum settings](Settings::checksum). 
+    pub fn checksum(mut self, checksum: Checksum) -> SettingsBuilder {
+        self.0.checksum = checksum;
+        self
+    }
+
+    /// Sets the [checksum seed](Settings::checksum_seed). 
+    pub fn checksum_seed(mut self, checksum_seed: u32) -> SettingsBuilder {
+        self.0.checksum_seed = checksum_seed;
+        self
+    }
+
+    /// Sets the [checksum byte order](Settings::checksum_order). 
+    pub fn checksum_order(mut self, checksum_order: Endianness) -> SettingsBuilder {
+        self.0.checksum_order = checksum_order;
+        self
+    }
+
+    /// Sets the [checksum chunk length](Settings::checksum_chunk). 
+    pub fn checksum_chunk(mut self, checksum_chunk: usize) -> SettingsBuilder {
+        self.0.checksum_chunk = checksum_chunk;
+        self
+    }
+
+    /// Sets whether to [decorate](Settings::decorate) the encoded string. 
+    pub fn decorate(mut self, decorate: bool) -> SettingsBuilder {
+        self.0.decorate = decorate;
+        self
+    }
+
+    /// Sets the [decorations](Settings::decoration) used. 
+    pub fn decoration(mut self, decoration: Decoration) -> SettingsBuilder {
+        self.0.decoration = decoration;
+        self
+    }
+
+    /// Sets whether to [uppercase](Settings::uppercase) the encoded string. 
+    pub fn uppercase(mut self, uppercase: bool) -> SettingsBuilder {
+        self.0.uppercase = uppercase;
+        self
+    }
+
+    /// Sets whether to perform [smoothing](Settings::smoothing). 
+    pub fn smoothing(mut self, smoothing: bool) -> SettingsBuilder {
+        self.0.smoothing = smoothing;
+        self
+    }
+
+    /// Sets the [smoothing seed](Settings::seed). 
+    pub fn seed(mut self, seed: u64) -> SettingsBuilder {
+        self.0.seed = seed;
+        self
+    }
+
+    /// Sets the key of the [whitening](Settings::whitening) pass. 
+    pub fn whitening(mut self, whitening: impl Into<Option<u64>>) -> SettingsBuilder {
+        self.0.whitening = whitening.into();
+        self
+    }
+
+    /// Sets the [alphabet](Settings::alphabet) used. 
+    pub fn alphabet(mut self, alphabet: Alphabet) -> SettingsBuilder {
+        self.0.alphabet = alphabet;
+        self
+    }
+
+    /// Validates and returns the settings. See [`Settings::validate`]. 
+    pub fn build(self) -> core::result::Result<Settings, SettingsError> {
+        self.0.validate().map(|()| self.0)
+    }
+}
+
+/// Error type for [validating](Settings::validate) settings. 
+#[derive(Error, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SettingsError {
+    /// The [minimum word length](Settings::min_word_len) exceeded the [maximum](Settings::word_len). 
+    #[error("Minimum word length {min} exceeded maximum word length {max}")]
+    MinExceedsMax {
+        /// The minimum word length. 
+        min: u8, 
+        /// The maximum word length. 
+        max: u8, 
+    }, 
+
+    /// The [separator](Settings::separator) could be part of a syllable, or is ignored within words, so it
+    /// would not break words when decoding. 
+    #[error("Separator {separator:?} could be part of a syllable or is ignored within words")]
+    Separator {
+        /// The separator. 
+        separator: char, 
+    }, 
+
+    /// The [padding](Settings::pad_syllables) could be part of a syllable, or is ignored within words, so it
+    /// would not break words when decoding. 
+    #[error("Padding {padding:?} could be part of a syllable or is ignored within words")]
+    Padding {
+        /// The padding character. 
+        padding: char, 
+    }, 
+
+    /// The [period](Decoration::period) is empty, could be part of a syllable or consists of only characters
+    /// ignored within words, so it would not break words when decoding. 
+    #[error("Period {period:?} is empty, could be part of a syllable or is ignored within words")]
+    Period {
+        /// The period. 
+        period: &'static str, 
+    }, 
+
+    /// The [comma](Decoration::comma) is empty, could be part of a syllable or consists of only characters
+    /// ignored within words, so it would not break words when decoding. 
+    #[error("Comma {comma:?} is empty, could be part of a syllable or is ignored within words")]
+    Comma {
+        /// The comma. 
+        comma: &'static str, 
+    }, 
+
+    /// The [checksum delimiter](Decoration::checksum) is empty, could be part of a syllable or consists of
+    /// only characters ignored within words, so it would not break words when decoding. 
+    #[error("Checksum delimiter {delimiter:?} is empty, could be part of a syllable or is ignored in words")]
+    ChecksumDelimiter {
+        /// The checksum delimiter. 
+        delimiter: &'static str, 
+    }, 
+
+    /// The [comma threshold](Decoration::comma_threshold) exceeded the
+    /// [period threshold](Decoration::period_threshold) in [hashed mode](DecorationMode::Hashed), so some
+    /// word-breaks would qualify for both; these get periods. 
+    #[error("Comma threshold {comma} exceeded period threshold {period}")]
+    Thresholds {
+        /// The comma threshold. 
+        comma: u32, 
+        /// The period threshold. 
+        period: u32, 
+    }, 
+}
+
+/// How syllables are grouped into words when encoding. See [`Settings::grouping`]. 
+/// 
+/// Regardless of the grouping, word-breaks required to avoid parsing ambiguities are always inserted, which
+/// can create shorter words. The decoder ignores word structure, so the grouping doesn't affect decoding. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Settings, WordGrouping};
+/// 
+/// let settings = Settings {
+///     grouping: WordGrouping::Fixed(3), 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"it's such a beautiful day", settings);
+/// let decoded = bunk::decode(encoded)?;
+/// 
+/// assert_eq!(decoded, b"it's such a beautiful day");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum WordGrouping {
+    /// Words are broken once they reach [`Settings::word_len`] syllables, but not before
+    /// [`Settings::min_word_len`] syllables. 
+    Variable, 
+    /// Words are broken after every `n` syllables counted from the start of the string, regardless of
+    /// [`Settings::word_len`] and [`Settings::min_word_len`]. This keeps the word-breaks aligned, e.g. when
+    /// printed on a recovery card. Since word-breaks required to avoid parsing ambiguities are inserted in
+    /// addition to these, the grouping is best-effort; a word interrupted by such a break is split in two
+    /// shorter words. A value of `0` disables word-breaks due to length. 
+    /// 
+    /// Since each syllable encodes exactly one byte, this starts a new group after every `n` bytes of input,
+    /// so each group can be checked against the bytes it encodes; the checksum forms the last group. 
+    Fixed(u8), 
+}
+
+impl Default for WordGrouping {
+    fn default() -> Self {
+        WordGrouping::Variable
+    }
+}
+
+/// Decorations used when [decorating](Settings::decorate) encoded strings. 
+/// 
+/// When a word-break is needed, it may be replaced with a period or a comma as determined by the
+/// [mode](DecorationMode). 
+/// 
+/// The delimiters may be any strings that don't contain alphabetic characters (or digits, for
+/// [alphabets](Alphabet) containing them), since those would be interpreted as syllables when decoding.
+/// They must also break words, so they can't be empty or consist of only characters
+/// [ignored within words](crate::decode_with_settings); see [`Settings::validate`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Decoration, Settings};
+/// 
+/// let settings = Settings {
+///     decorate: true, 
+///     decoration: Decoration {
+///         period: "! ", 
+///         comma: "; ", 
+///         ..Default::default()
+///     }, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"it's such a beautiful day", settings);
+/// let decoded = bunk::decode(encoded)?;
+/// 
+/// assert_eq!(decoded, b"it's such a beautiful day");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Decoration {
+    /// Delimiter ending a sentence. The next word is capitalised, and the delimiter --- with trailing
+    /// whitespace removed --- also ends the encoded string. Default: `". "`. 
+    pub period: &'static str, 
+    /// Delimiter separating clauses in a sentence. Default: `", "`. 
+    pub comma: &'static str, 
+    /// Delimiter set before the [checksum](Settings::checksum) syllables, e.g. `" -- "`, so that a reader can
+    /// tell them apart from the payload. Like the period and comma, it must break words; see
+    /// [`Settings::validate`]. If `None`, an ordinary word-break is used. Default: `None`. 
+    pub checksum: Option<&'static str>, 
+    /// How word-breaks are chosen to be replaced with periods or commas. Default:
+    /// [`DecorationMode::Hashed`]. 
+    pub mode: DecorationMode, 
+    /// In [hashed mode](DecorationMode::Hashed), a word-break is replaced with a
+    /// [period](Decoration::period) if the number of ones in the 32-bit hash, from 0 to 32, is greater than
+    /// this. Raise it for longer sentences; `32` disables periods between words. Default: `19`. 
+    pub period_threshold: u32, 
+    /// In [hashed mode](DecorationMode::Hashed), a word-break is replaced with a [comma](Decoration::comma)
+    /// if the number of ones in the hash is less than this. Lower it for fewer commas; `0` disables them.
+    /// This must not exceed the [period threshold](Decoration::period_threshold); see
+    /// [`Settings::validate`]. Default: `14`. 
+    pub comma_threshold: u32, 
+}
+
+impl Default for Decoration {
+    fn default() -> Self {
+        Decoration::DEFAULT
+    }
+}
+
+impl Decoration {
+    /// The default decorations, for use in `const` contexts. 
+    const DEFAULT: Decoration = Decoration {
+        period: ". ", 
+        comma: ", ", 
+        checksum: None, 
+        mode: DecorationMode::Hashed, 
+        period_threshold: 19, 
+        comma_threshold: 14, 
+    };
+}
+
+/// Specifies how word-breaks are chosen to be replaced with periods or commas when
+/// [decorating](Settings::decorate). 
+/// 
+/// Default: [`DecorationMode::Hashed`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Decoration, DecorationMode, Settings};
+/// 
+/// let settings = Settings {
+///     decorate: true, 
+///     decoration: Decoration {
+///         mode: DecorationMode::Periodic { sentence_len: 4, clause_len: 2 }, 
+///         ..Default::default()
+///     }, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"it's such a beautiful day", settings);
+/// let decoded = bunk::decode(encoded)?;
+/// 
+/// assert_eq!(decoded, b"it's such a beautiful day");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum DecorationMode {
+    /// Decorations are determined by the number of ones in the binary representation of a hash of the data
+    /// encoded so far, which is between 0 and 32; see [`Decoration::period_threshold`] and
+    /// [`Decoration::comma_threshold`]. This makes the decorations appear random, while still being
+    /// deterministic. 
+    Hashed, 
+    /// Decorations are determined by the position of the word, independent of the data. This gives the
+    /// encoded string a regular rhythm. 
+    Periodic {
+        /// Every word-break following this many words is replaced with a period. `0` disables periods. 
+        sentence_len: u8, 
+        /// Every word-break following this many words is replaced with a comma, unless it is replaced with
+        /// a period. `0` disables commas. 
+        clause_len: u8, 
+    }, 
+}
+
+impl Default for DecorationMode {
+    fn default() -> Self {
+        DecorationMode::Hashed
     }
 }
 
@@ -43,8 +625,18 @@ pub fn encode(data: impl AsRef<[u8]>) -> String {
 
 /// Encodes data using given [settings](Settings). 
 /// 
-/// The data can then be decoded using [`decode_with_settings`]. Note that the [checksum settings](Checksum)
-/// used when decoding must match the ones [used here](Settings::checksum). 
+/// The data can then be decoded using [`decode_with_settings`]. Note that the
+/// [decoding settings](DecodeSettings) must match the settings used here. 
+/// 
+/// The string is allocated once with a capacity of [`encoded_len`], so that it never grows while encoding.
+/// Since that is an upper bound, the capacity typically exceeds the length by about 70%; use
+/// [`String::shrink_to_fit`] if the string is kept around and memory is tight. 
+/// 
+/// # Panics
+/// 
+/// In debug builds, panics if the settings fail [validation](Settings::validate) in a way that would make
+/// the output undecodable, e.g. with a [separator](Settings::separator) that could be part of a syllable.
+/// The tolerated [`SettingsError::MinExceedsMax`] and [`SettingsError::Thresholds`] don't panic. 
 /// 
 /// # Examples
 /// 
@@ -78,109 +670,1376 @@ pub fn encode(data: impl AsRef<[u8]>) -> String {
 /// # Ok::<(), bunk::InvalidData>(())
 /// ```
 pub fn encode_with_settings(data: impl AsRef<[u8]>, settings: Settings) -> String {
+    let mut string = String::new();
+    encode_into(data, settings, &mut string);
+    string
+}
+
+/// Encodes data using given [settings](Settings), without allocating if the encoded string is empty. 
+/// 
+/// The encoded string is only empty for empty data without a [checksum](Settings::checksum); it's then
+/// borrowed, and otherwise owned. This is useful for encoding fields that are often empty. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use std::borrow::Cow;
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Disabled, 
+///     ..Default::default()
+/// };
+/// 
+/// assert!(matches!(bunk::encode_cow([], settings), Cow::Borrowed("")));
+/// assert_eq!(bunk::encode_cow(b"aftersun", settings), bunk::encode_with_settings(b"aftersun", settings));
+/// ```
+pub fn encode_cow(data: impl AsRef<[u8]>, settings: Settings) -> Cow<'static, str> {
+    let data = data.as_ref();
+
+    match data.is_empty() && settings.checksum.len_for(0) == 0 {
+        true => Cow::Borrowed(""), 
+        false => Cow::Owned(encode_with_settings(data, settings)), 
+    }
+}
+
+/// Encodes data using given [settings](Settings) without any characters between words, for transports that
+/// strip or collapse whitespace. 
+/// 
+/// Simply removing the spaces from an encoded string doesn't work in general: the word breaks are what lets
+/// the decoder tell e.g. "o us" from "ous". Instead, each word is capitalized, so that the breaks survive as
+/// the case of the letters, yielding strings like "TriroriMulry". [Decorating](Settings::decorate),
+/// [uppercasing](Settings::uppercase) and [padding](Settings::pad_syllables) are disabled, and the
+/// [separator](Settings::separator) is unused. 
+/// Decode the string with [`decode_delimiterless`](crate::decode_delimiterless). 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let encoded = bunk::encode_delimiterless(b"aftersun", Settings::default());
+/// 
+/// assert!(encoded.chars().all(|char| char.is_ascii_alphabetic()));
+/// assert_eq!(bunk::decode_delimiterless(&encoded, Checksum::default())?, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn encode_delimiterless(data: impl AsRef<[u8]>, settings: Settings) -> String {
     // factored out non-generic code to reduce code size
-    encode_mono(data.as_ref(), settings)
+    encode_delimiterless_mono(data.as_ref(), settings)
 }
 
-/// Monomorphised encode implementation.  
+/// Monomorphised delimiterless encode implementation. 
 #[inline(never)]
-fn encode_mono(data: &[u8], settings: Settings) -> String {
-    let Settings{ word_len: max_word, checksum, decorate } = settings;
-    
-    let mut sentence = Sentence {
-        buffer: Vec::with_capacity(3 * (data.len() + checksum.len())), 
-        previous: None, 
-        word_len: 0, 
-        max_word: max_word.unwrap_or(u8::MAX), 
-        decorate, 
+fn encode_delimiterless_mono(data: &[u8], settings: Settings) -> String {
+    let settings = Settings {
+        separator: ' ', 
+        pad_syllables: None, 
+        decorate: false, 
+        uppercase: false, 
+        ..settings
     };
-    let mut hash = Fnv1a::new();
+    let encoded = encode_with_settings(data, settings);
+    let mut string = String::with_capacity(encoded.len());
 
-    // encode the payload
-    for (i, &byte) in data.iter().enumerate() {
-        hash.update(byte);
-        let encoded = running_code(byte, i);
-        sentence.push(encoded, hash);
-    }
+    for word in encoded.split(' ') {
+        let mut chars = word.chars();
 
-    let checksum_len = checksum.len();
-    let checksum_bytes = hash.bytes();
-    
-    // encode the checksum
-    for &byte in &checksum_bytes[..checksum_len] {
-        // the hash is updated here only to be used as seed for the sentence encoder
-        hash.update(byte);
-        sentence.push(byte, hash);
+        if let Some(first) = chars.next() {
+            string.push(first.to_ascii_uppercase());
+            string.push_str(chars.as_str());
+        }
     }
+    string
+}
 
-    let buffer = sentence.finalise();
-    String::from_utf8(buffer).expect("All syllables are valid UTF-8")
+/// Encodes data using given [settings](Settings) into an existing string buffer. 
+/// 
+/// The buffer is cleared before encoding, but its capacity is reused. This is useful when encoding many
+/// values in a loop, since it avoids allocating a new string for each one. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let mut buffer = String::new();
+/// 
+/// for data in [b"after", b"sunny"] {
+///     bunk::encode_into(data, Settings::default(), &mut buffer);
+///     let decoded = bunk::decode(&buffer)?;
+/// 
+///     assert_eq!(decoded, data);
+/// }
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn encode_into(data: impl AsRef<[u8]>, settings: Settings, out: &mut String) {
+    // factored out non-generic code to reduce code size
+    encode_mono(data.as_ref(), settings, out)
 }
 
-/// Encodes bytes as a string of syllables one-by-one. 
+/// Encodes a `u128`, e.g. a numeric identifier, using given [settings](Settings). 
 /// 
-/// Does not perform the [`running_code`] or compute a checksum; that is handled in [`encode_mono`]. 
-struct Sentence {
-    /// Encode ascii-string so far. 
-    buffer: Vec<u8>, 
-    /// Previous syllable. Used for detecting ambiguity. 
-    previous: Option<&'static [u8]>, 
-    /// Current word length in syllables. 
-    word_len: u8, 
-    /// Maximum allowed word length in syllables. 
-    max_word: u8, 
-    /// Whether the sentence should be decorated with periods, commas, and sentence casing. 
-    decorate: bool, 
+/// The integer is encoded as its 16 bytes in little-endian order, i.e., the same as encoding
+/// `id.to_le_bytes()`. Decode it with [`decode_u128`](crate::decode_u128). 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let id = 0x6166_7465_7273_756e_2069_7473_2073_7563;
+/// let encoded = bunk::encode_u128(id, Settings::default());
+/// 
+/// assert_eq!(encoded, bunk::encode(id.to_le_bytes()));
+/// assert_eq!(bunk::decode_u128(encoded, Checksum::default())?, id);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn encode_u128(id: u128, settings: Settings) -> String {
+    encode_with_settings(id.to_le_bytes(), settings)
 }
 
-impl Sentence {
-    /// Encodes a single byte. The seed is used to inform whether a word-break space should be replaced with
-    /// a comma or period. 
-    fn push(&mut self, byte: u8, seed: Fnv1a) {
-        // get syllable corresponding to byte and determine whether we need a word-break before we add it
-        let syllable = syllables::get(byte);
-        let ambiguous = |preceding| {
-            // there is a parsing ambiguity if the first char of the next syllable is a valid continuation of
-            // the previous syllable
-            let next = syllable[0];
-            syllables::char_follows(next, preceding)
-        }; 
-        let word_break = self.word_len >= self.max_word || self.previous.is_some_and(ambiguous);
-        
-        let seed = seed.0.count_ones();
-        let (capitalise, delim): (bool, Option<&[u8]>) = match (word_break, self.decorate) {
-            // if we're decorating, replace a word-break space with a period or comma with some probability
-            (true, true) if seed > 19 => (true,  Some(b". ")), 
-            (true, true) if seed < 14 => (false, Some(b", ")), 
-            // else, just use a space if we need a word-break
-            (true, _)      => (false, Some(b" ")), 
-            (false, true)  => (self.buffer.is_empty(), None), 
-            (false, false) => (false, None), 
-        };
+/// Encodes a `u64`, e.g. a numeric identifier, using given [settings](Settings). 
+/// 
+/// The integer is encoded as its 8 bytes in little-endian order, i.e., the same as encoding
+/// `id.to_le_bytes()`. Decode it with [`decode_u64`](crate::decode_u64). 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let id = 0x6166_7465_7273_756e;
+/// let encoded = bunk::encode_u64(id, Settings::default());
+/// 
+/// assert_eq!(encoded, bunk::encode(id.to_le_bytes()));
+/// assert_eq!(bunk::decode_u64(encoded, Checksum::default())?, id);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn encode_u64(id: u64, settings: Settings) -> String {
+    encode_with_settings(id.to_le_bytes(), settings)
+}
 
-        // if there's a delimiter (e.g. space or comma), add it before the syllable and reset ambiguity
-        // control vars
-        if let Some(delim) = delim {
-            self.word_len = 0;
-            self.previous = None;
-            self.buffer.reserve(delim.len() + syllable.len());
-            self.buffer.extend_from_slice(delim);
-        }
-        self.buffer.extend_from_slice(syllable);
-        self.previous = Some(syllable);
-        self.word_len += 1;
-        
-        if capitalise {
-            let first = self.buffer.len() - syllable.len();
-            self.buffer[first] = self.buffer[first].to_ascii_uppercase();
-        }
-    }
+/// Encodes a [UUID](uuid::Uuid) using given [settings](Settings). Requires the `uuid` feature. 
+/// 
+/// The UUID is encoded as its 16 bytes in big-endian order, as returned by
+/// [`Uuid::as_bytes`](uuid::Uuid::as_bytes). Note that this differs from [`encode_u128`] of
+/// [`Uuid::as_u128`](uuid::Uuid::as_u128), which is little-endian. Decode it with
+/// [`decode_uuid`](crate::decode_uuid). 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// use uuid::Uuid;
+/// 
+/// let uuid = Uuid::from_u128(0x6166_7465_7273_756e_2069_7473_2073_7563);
+/// let encoded = bunk::encode_uuid(uuid, Settings::default());
+/// 
+/// assert_eq!(encoded, bunk::encode(uuid.as_bytes()));
+/// assert_eq!(bunk::decode_uuid(encoded, Checksum::default())?, uuid);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[cfg(feature = "uuid")]
+pub fn encode_uuid(uuid: uuid::Uuid, settings: Settings) -> String {
+    encode_with_settings(uuid.as_bytes(), settings)
+}
 
-    /// Performs final decorations, should there be any, and returns the encoded ascii string. 
-    fn finalise(mut self) -> Vec<u8> {
-        if self.decorate && !self.buffer.is_empty() {
-            self.buffer.push(b'.');
-        }
-        self.buffer
+/// Encodes a short fingerprint of data using given [settings](Settings), e.g. for comparing keys by eye. 
+/// 
+/// Only the hash otherwise used as [checksum](Settings::checksum) is encoded, not the data itself, yielding
+/// a tag of fixed length for arbitrarily large data. The length of the hash is that of the checksum, so use
+/// at least [`Checksum::Length4`] to make collisions unlikely; no checksum is added to the fingerprint
+/// itself. The [checksum seed](Settings::checksum_seed) is used as for checksums. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Length4, 
+///     ..Default::default()
+/// };
+/// let key = [33; 2048];
+/// let fingerprint = bunk::fingerprint(key, settings);
+/// 
+/// assert_eq!(bunk::decode_with_settings(&fingerprint, Checksum::Disabled)?.len(), 4);
+/// assert_ne!(fingerprint, bunk::fingerprint([34; 2048], settings));
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn fingerprint(data: impl AsRef<[u8]>, settings: Settings) -> String {
+    // factored out non-generic code to reduce code size
+    fingerprint_mono(data.as_ref(), settings)
+}
+
+/// Monomorphised fingerprint implementation. 
+#[inline(never)]
+fn fingerprint_mono(data: &[u8], settings: Settings) -> String {
+    let mut hash = Fnv1a::new_seeded(settings.checksum_seed);
+
+    for &byte in data {
+        hash.update(byte);
     }
+    let hash = hash.bytes(settings.checksum, settings.checksum_order);
+    let hash = &hash[..settings.checksum.len_for(data.len())];
+    let settings = Settings {
+        checksum: Checksum::Disabled, 
+        ..settings
+    };
+    encode_with_settings(hash, settings)
 }
+
+/// Encodes each of a list of items using given [settings](Settings). 
+/// 
+/// A single scratch buffer is reused while encoding the items, so that each encoded string is allocated
+/// only once with its exact length. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let encoded = bunk::encode_batch(&[&b"after"[..], b"sun"], Settings::default());
+/// 
+/// assert_eq!(encoded, [bunk::encode(b"after"), bunk::encode(b"sun")]);
+/// ```
+pub fn encode_batch<T: AsRef<[u8]>>(items: &[T], settings: Settings) -> Vec<String> {
+    let mut scratch = Vec::new();
+    items
+        .iter()
+        .map(|item| encode_scratch(item.as_ref(), settings, &mut scratch))
+        .collect()
+}
+
+/// Encodes each of a list of items using given [settings](Settings) in parallel. 
+/// 
+/// Equivalent to [`encode_batch`], but the items are distributed over the threads of the global
+/// [rayon](https://docs.rs/rayon) thread pool, each thread reusing its own scratch buffer. Requires the
+/// `rayon` feature. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let items: Vec<_> = (0..1000u32).map(u32::to_le_bytes).collect();
+/// let encoded = bunk::par_encode_batch(&items, Settings::default());
+/// 
+/// assert_eq!(encoded, bunk::encode_batch(&items, Settings::default()));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_encode_batch<T>(items: &[T], settings: Settings) -> Vec<String>
+where
+    T: AsRef<[u8]> + Sync
+{
+    use rayon::prelude::*;
+
+    items
+        .par_iter()
+        .map_init(Vec::new, |scratch, item| encode_scratch(item.as_ref(), settings, scratch))
+        .collect()
+}
+
+/// Returns an upper bound on the length of the string produced when encoding `input_len` bytes with given
+/// [settings](Settings). 
+/// 
+/// The bound assumes the worst case for every syllable: that it is 4 letters long and preceded by a
+/// word-break. For undecorated strings, each word-break is a single space; for decorated strings, it may
+/// be a [comma or period](Decoration), and a final period is added. The bound is therefore not tight
+/// --- actual encodings are on average considerably shorter --- but it is guaranteed never to be exceeded.
+/// This is useful for pre-sizing buffers and display fields. Saturates at [`usize::MAX`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let settings = Settings::default();
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// 
+/// assert!(encoded.len() <= bunk::encoded_len(8, settings));
+/// assert_eq!(bunk::encoded_len(usize::MAX, settings), usize::MAX);
+/// ```
+pub const fn encoded_len(input_len: usize, settings: Settings) -> usize {
+    encoded_len_with(input_len, settings, syllables::MAX_LEN)
+}
+
+/// Like [`encoded_len`], but for syllables of up to `max_len` letters, e.g. those of a custom
+/// [table](Table). 
+const fn encoded_len_with(input_len: usize, settings: Settings, max_len: usize) -> usize {
+    let syllables = settings.checksum.chunked_len(input_len, settings.checksum_chunk);
+
+    if syllables == 0 {
+        return 0
+    }
+    let Decoration{ period, comma, checksum, .. } = settings.decoration;
+    let separator_len = settings.separator.len_utf8();
+    let (delim_len, end_len) = match settings.decorate {
+        true if period.len() > comma.len() => (period.len(), period.len()), 
+        true => (comma.len(), period.len()), 
+        false => (separator_len, 0), 
+    };
+    let delim_len = if separator_len > delim_len { separator_len } else { delim_len };
+    // each chunk has its own checksum, and so its own checksum delimiter
+    let checksums = match settings.checksum_chunk {
+        0 => 1, 
+        chunk_len => input_len.div_ceil(chunk_len), 
+    };
+    let checksum_len = match (settings.decorate, checksum) {
+        (true, Some(checksum)) => checksums.saturating_mul(checksum.len()), 
+        _ => 0, 
+    };
+    let syllable_len = match settings.pad_syllables {
+        // a padded syllable may consist of a single letter and three multi-byte padding characters
+        Some(pad) => 1 + (syllables::MAX_LEN - 1) * pad.len_utf8(), 
+        None => syllables::MAX_LEN, 
+    };
+    let syllable_len = if syllable_len > max_len { syllable_len } else { max_len };
+    syllables
+        .saturating_mul(syllable_len)
+        .saturating_add((syllables - 1).saturating_mul(delim_len))
+        .saturating_add(checksum_len)
+        .saturating_add(end_len)
+}
+
+/// Estimates the number of bytes that can be encoded with given [settings](Settings) into a string of at
+/// most `output_len` bytes. Roughly the inverse of [`encoded_len`]. 
+/// 
+/// Unlike [`encoded_len`], this is an estimate rather than a bound: it assumes every syllable has the
+/// average length of the syllables of the [alphabet](Settings::alphabet) --- about 2.47 letters for the
+/// standard alphabet --- and that words are broken after [`word_len`](Settings::word_len) syllables. Since
+/// syllable lengths vary by byte, the encoding of a particular payload of the estimated length may be
+/// longer or shorter than `output_len`; use [`encoded_len`] when it must fit. This is useful for choosing
+/// key sizes that fit a display budget. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let settings = Settings::default();
+/// let payload_len = bunk::max_payload_for(60, settings);
+/// 
+/// // 20 bytes encode to about 60 bytes on average
+/// assert_eq!(payload_len, 20);
+/// ```
+pub fn max_payload_for(output_len: usize, settings: Settings) -> usize {
+    // costs are summed over all 256 syllables, i.e. measured in 256ths of a byte per syllable
+    let letters: usize = (0..=u8::MAX)
+        .map(|byte| syllables::get(settings.alphabet, byte).len())
+        .sum();
+    let syllable_cost = match settings.pad_syllables {
+        Some(pad) => letters + (256 * syllables::MAX_LEN - letters) * pad.len_utf8(), 
+        None => letters, 
+    };
+    // the minimum only postpones length-breaks, so it doesn't introduce any if they're disabled
+    let word_len = match (settings.grouping, settings.word_len) {
+        (WordGrouping::Variable, None | Some(0)) => None, 
+        (WordGrouping::Variable, Some(max)) => Some(settings.min_word_len.map_or(max, |min| max.max(min))), 
+        (WordGrouping::Fixed(n), _) => Some(n), 
+    };
+    // words without length-breaks are still broken to avoid parsing ambiguities, every ~32 syllables
+    let word_len = match word_len {
+        None | Some(0) => 32, 
+        Some(n) => n as usize, 
+    };
+    let Decoration{ period, comma, checksum, .. } = settings.decoration;
+    let separator_len = settings.separator.len_utf8();
+    let (delim_len, overhead) = match settings.decorate {
+        // each word-break is assumed equally likely to be a separator, comma or period
+        true => (separator_len + comma.len() + period.len(), period.len() + checksum.map_or(0, str::len)), 
+        false => (separator_len * 3, 0), 
+    };
+    let delim_cost = delim_len * 256 / (3 * word_len);
+    let available = match output_len.checked_sub(overhead) {
+        Some(available) => available.saturating_mul(256), 
+        None => return 0, 
+    };
+    // the last syllable isn't followed by a word-break
+    let syllables = available.saturating_add(delim_cost) / (syllable_cost + delim_cost);
+    (0..=syllables)
+        .rev()
+        .find_map(|total_len| settings.checksum.chunked_payload_len(total_len, settings.checksum_chunk))
+        .unwrap_or(0)
+}
+
+/// Encodes data using given [settings](Settings) and a custom syllable [table](Table). 
+/// 
+/// The encoded string must be decoded with the same table using [`decode_with_table`]. Note that
+/// [`encoded_len`] doesn't account for tables with syllables longer than those of the built-in table. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Settings, Table};
+/// 
+/// let syllables: [String; 256] = std::array::from_fn(|i| format!("x{}", bunk::syllable(i as u8)));
+/// let table = Table::from_syllables(std::array::from_fn(|i| syllables[i].as_str()))?;
+/// 
+/// let encoded = bunk::encode_with_table(b"aftersun", &table, Settings::default());
+/// 
+/// assert!(encoded.split(' ').all(|word| word.starts_with('x')));
+/// # Ok::<(), bunk::TableError>(())
+/// ```
+pub fn encode_with_table(data: impl AsRef<[u8]>, table: &Table, settings: Settings) -> String {
+    // factored out non-generic code to reduce code size
+    encode_table_mono(data.as_ref(), table, settings)
+}
+
+/// Encodes `len` random bytes using given [settings](Settings). 
+/// 
+/// The bytes are drawn from the thread-local random number generator. This is intended for tests, demos
+/// and fuzzing; use [`random_with_rng`] to pick the generator or to also get the bytes. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let encoded = bunk::random(16, Settings::default());
+/// let decoded = bunk::decode(encoded)?;
+/// 
+/// assert_eq!(decoded.len(), 16);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[cfg(feature = "rand")]
+pub fn random(len: usize, settings: Settings) -> String {
+    random_with_rng(len, settings, &mut rand::thread_rng()).1
+}
+
+/// Encodes `len` random bytes drawn from given random number generator using given [settings](Settings). 
+/// 
+/// Returns the bytes along with their encoding. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// use rand::{rngs::StdRng, SeedableRng};
+/// 
+/// let (data, encoded) = bunk::random_with_rng(16, Settings::default(), &mut StdRng::seed_from_u64(0));
+/// 
+/// assert_eq!(bunk::decode(encoded)?, data);
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[cfg(feature = "rand")]
+pub fn random_with_rng<R>(len: usize, settings: Settings, rng: &mut R) -> (Vec<u8>, String)
+where
+    R: rand::RngCore + ?Sized
+{
+    let mut data = alloc::vec![0; len];
+    rng.fill_bytes(&mut data);
+    let encoded = encode_with_settings(&data, settings);
+    (data, encoded)
+}
+
+/// Encodes data using given [settings](Settings) directly into a writer. 
+/// 
+/// Syllables and delimiters are written one-by-one as they are produced, so the encoded string is never
+/// held in memory in its entirety. Since this results in many small writes, wrapping unbuffered writers
+/// (such as files and sockets) in a [`BufWriter`](io::BufWriter) is recommended. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let mut output = Vec::new();
+/// bunk::encode_to_writer(b"aftersun", Settings::default(), &mut output)?;
+/// 
+/// assert_eq!(output, bunk::encode(b"aftersun").as_bytes());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn encode_to_writer<W>(data: impl AsRef<[u8]>, settings: Settings, writer: &mut W) -> io::Result<()>
+where
+    W: io::Write
+{
+    let mut output = IoOutput(writer);
+    let mut stream = Stream::new(settings, settings.alphabet);
+    stream.push(data.as_ref(), &mut output)?;
+    stream.finish(&mut output)
+}
+
+/// Encodes data using given [settings](Settings) directly into a [`fmt::Write`]. 
+/// 
+/// This is the `no_std` counterpart of [`encode_to_writer`]; syllables and delimiters are written one-by-one
+/// as they are produced, e.g. into a [`fmt::Formatter`] or a fixed-capacity string. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let mut output = String::new();
+/// bunk::encode_to_fmt(b"aftersun", Settings::default(), &mut output)?;
+/// 
+/// assert_eq!(output, bunk::encode(b"aftersun"));
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub fn encode_to_fmt<W>(data: impl AsRef<[u8]>, settings: Settings, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write
+{
+    let mut output = FmtOutput(writer);
+    let mut stream = Stream::new(settings, settings.alphabet);
+    stream.push(data.as_ref(), &mut output)?;
+    stream.finish(&mut output)
+}
+
+/// Lazily encodes data using given [settings](Settings) as a sequence of [tokens](Token). 
+/// 
+/// This exposes the structure of the encoded string, allowing for custom rendering, e.g. coloring alternate
+/// words or inserting line breaks at word boundaries. Concatenating the syllables with a space, comma,
+/// period, or checksum delimiter (as per the [decoration](Settings::decoration)) for each break yields the
+/// same string as [`encode_with_settings`], except that the syllables are never capitalised or padded. When
+/// decorating, a final [`BreakKind::Period`] ends the sequence. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{BreakKind, Settings, Token};
+/// 
+/// let encoded: String = bunk::encode_iter(b"aftersun", Settings::default())
+///     .map(|token| match token {
+///         Token::Syllable(syllable) => std::str::from_utf8(syllable).unwrap(), 
+///         Token::Break(_) => " ", 
+///     })
+///     .collect();
+/// 
+/// assert_eq!(encoded, bunk::encode(b"aftersun"));
+/// ```
+pub fn encode_iter(data: impl AsRef<[u8]>, settings: Settings) -> impl Iterator<Item = Token> {
+    Tokens {
+        data, 
+        stream: Stream::new(settings, settings.alphabet), 
+        checksum: None, 
+        syllable: None, 
+        finished: false, 
+        done: false, 
+    }
+}
+
+/// Element of an encoded string. See [`encode_iter`]. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Token {
+    /// An ascii syllable encoding a single byte. 
+    Syllable(&'static [u8]), 
+    /// A break between two words, or the end of the final sentence when decorating. 
+    Break(BreakKind), 
+}
+
+/// Kind of break between two words. See [`Token::Break`]. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BreakKind {
+    /// Plain word-break; rendered as a space. 
+    Space, 
+    /// Rendered as [`Decoration::comma`]. Only produced when [decorating](Settings::decorate). 
+    Comma, 
+    /// Rendered as [`Decoration::period`]. Only produced when [decorating](Settings::decorate). 
+    Period, 
+    /// Rendered as [`Decoration::checksum`], before the checksum syllables. Only produced when
+    /// [decorating](Settings::decorate) with a checksum delimiter. 
+    Checksum, 
+}
+
+/// Returns the indices of the syllables after which a word-break is inserted when encoding data using given
+/// [settings](Settings). 
+/// 
+/// This reveals the structure chosen by the encoder, e.g. to diagnose why a string is broken into words the
+/// way it is; a break is inserted either because the word reached its maximum length or to avoid a parsing
+/// ambiguity. Each syllable encodes a byte of the data, or of the checksum following it, so without
+/// [chunked checksums](Settings::checksum_chunk), these are also the indices of the bytes of the data, and
+/// indices from its length onwards refer to the checksum. The final period added when
+/// [decorating](Settings::decorate) isn't a word-break; use [`encode_iter`] to also get the kind of each
+/// break. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// // encoded as "trirori mulry", the last syllable being the checksum
+/// let breaks = bunk::break_positions([0, 0, 0, 0], Settings::default());
+/// 
+/// assert_eq!(breaks, [2]);
+/// ```
+pub fn break_positions(data: impl AsRef<[u8]>, settings: Settings) -> Vec<usize> {
+    // factored out non-generic code to reduce code size
+    break_positions_mono(data.as_ref(), settings)
+}
+
+#[inline(never)]
+fn break_positions_mono(data: &[u8], settings: Settings) -> Vec<usize> {
+    let mut breaks = Vec::new();
+    let mut syllables = 0;
+
+    // the first syllable is never preceded by a word-break, so the index of the preceding one is never -1
+    for token in encode_iter(data, settings) {
+        match token {
+            Token::Syllable(_) => syllables += 1, 
+            Token::Break(_) => breaks.push(syllables), 
+        }
+    }
+    // the final period follows the last syllable
+    breaks.retain(|&index| index < syllables);
+    breaks.iter_mut().for_each(|index| *index -= 1);
+    breaks
+}
+
+/// Encodes data using given [settings](Settings) into a fixed-capacity [`heapless::String`]. 
+/// 
+/// This allows encoding without allocating, e.g. on embedded targets with little heap to spare. The crate
+/// still links `alloc`, so a global allocator is required nonetheless. [`encoded_len`] gives an upper bound
+/// of the capacity `N` needed to fit the encoded string. 
+/// 
+/// # Errors
+/// 
+/// Returns [`CapacityError`] if the encoded string doesn't fit in `N` bytes. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{CapacityError, Settings};
+/// 
+/// let encoded = bunk::encode_heapless::<64>(b"aftersun", Settings::default())?;
+/// assert_eq!(encoded, bunk::encode(b"aftersun").as_str());
+/// 
+/// let too_small = bunk::encode_heapless::<8>(b"aftersun", Settings::default());
+/// assert_eq!(too_small, Err(CapacityError));
+/// # Ok::<(), CapacityError>(())
+/// ```
+#[cfg(feature = "heapless")]
+pub fn encode_heapless<const N: usize>(
+    data: impl AsRef<[u8]>, 
+    settings: Settings, 
+) -> core::result::Result<heapless::String<N>, CapacityError> {
+    let mut output = heapless::String::new();
+    let mut stream = Stream::new(settings, settings.alphabet);
+    stream.push(data.as_ref(), &mut output)?;
+    stream.finish(&mut output)?;
+    Ok(output)
+}
+
+/// Error returned when an encoded string doesn't fit in a fixed-capacity buffer. See [`encode_heapless`]. 
+#[cfg(feature = "heapless")]
+#[derive(Error, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[error("Encoded string exceeded the capacity of the buffer")]
+pub struct CapacityError;
+
+/// Lazily encoded data. 
+/// 
+/// Encodes the data using given [settings](Settings) when formatted with [`Display`](fmt::Display), writing
+/// directly to the formatter. This avoids allocating an intermediate string when the encoded data is only
+/// to be formatted, e.g. when logging. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Encoded, Settings};
+/// 
+/// let encoded = Encoded::new(b"aftersun", Settings::default());
+/// let formatted = format!("key: {encoded}");
+/// 
+/// assert_eq!(formatted, format!("key: {}", bunk::encode(b"aftersun")));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Encoded<'a>(&'a [u8], Settings);
+
+impl<'a> Encoded<'a> {
+    /// Wraps data to be encoded using given [settings](Settings). 
+    pub fn new(data: &'a [u8], settings: Settings) -> Encoded<'a> {
+        Encoded(data, settings)
+    }
+}
+
+impl fmt::Display for Encoded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = FmtOutput(f);
+        let mut stream = Stream::new(self.1, self.1.alphabet);
+        stream.push(self.0, &mut output)?;
+        stream.finish(&mut output)
+    }
+}
+
+/// Monomorphised encode implementation.  
+#[inline(never)]
+fn encode_mono(data: &[u8], settings: Settings, out: &mut String) {
+    debug_assert!(
+        matches!(
+            settings.validate(), 
+            Ok(()) | Err(SettingsError::MinExceedsMax { .. } | SettingsError::Thresholds { .. }), 
+        ), 
+        "settings yield undecodable output: {:?}", settings.validate(), 
+    );
+    // take ownership of the string's buffer to push bytes to it
+    let mut buffer = mem::take(out).into_bytes();
+    buffer.clear();
+
+    // reserve the upper bound of the output length so that the buffer never grows while encoding. counting
+    // the exact length in a first pass would cost about as much as the encoding itself. the bound is about
+    // 1.7 times the average length, which is left as spare capacity rather than reallocating to shrink it
+    buffer.reserve(encoded_len(data.len(), settings));
+
+    let mut encoder = Encoder {
+        stream: Stream::new(settings, settings.alphabet), 
+        buffer, 
+    };
+    encoder.push(data);
+    *out = encoder.finish();
+}
+
+/// Monomorphised encode implementation for custom tables. 
+#[inline(never)]
+fn encode_table_mono(data: &[u8], table: &Table, settings: Settings) -> String {
+    // like `encode_mono`, reserve the upper bound, accounting for syllables longer than the built-in ones
+    let mut buffer = Vec::with_capacity(encoded_len_with(data.len(), settings, table.max_len()));
+    let mut stream = Stream::new(settings, table);
+    let Ok(()) = stream.push(data, &mut buffer);
+    let Ok(()) = stream.finish(&mut buffer);
+    into_string(buffer)
+}
+
+/// Encodes data into a scratch buffer, then copies it into a newly allocated string of the exact length. 
+#[inline(never)]
+fn encode_scratch(data: &[u8], settings: Settings, scratch: &mut Vec<u8>) -> String {
+    scratch.clear();
+    let mut stream = Stream::new(settings, settings.alphabet);
+    let Ok(()) = stream.push(data, scratch);
+    let Ok(()) = stream.finish(scratch);
+    into_string(scratch.clone())
+}
+
+/// Converts an encoded buffer into a string without validating it as UTF-8, which would otherwise walk the
+/// entire buffer. 
+fn into_string(buffer: Vec<u8>) -> String {
+    debug_assert!(core::str::from_utf8(&buffer).is_ok());
+
+    // SAFETY: everything written to the buffer is either (part of) a syllable, all of which are ascii, or a
+    // delimiter, which is a `&str`; the buffer is therefore always valid UTF-8. note that a syllable is
+    // only ever split after its first letter, which is an ascii character
+    unsafe { String::from_utf8_unchecked(buffer) }
+}
+
+/// Incrementally encodes data fed to it in chunks. 
+/// 
+/// This is useful when the data to be encoded isn't available all at once, e.g. when it is received from a
+/// device or over a network. Feeding data to the encoder in any number of chunks yields the same result as
+/// encoding the concatenated data with [`encode_with_settings`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Encoder, Settings};
+/// 
+/// let mut encoder = Encoder::new(Settings::default());
+/// encoder.push(b"after");
+/// encoder.push(b"sun");
+/// let encoded = encoder.finish();
+/// 
+/// assert_eq!(encoded, bunk::encode(b"aftersun"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    /// State of the encoding. 
+    stream: Stream, 
+    /// Encoded string so far. 
+    buffer: Vec<u8>, 
+}
+
+impl Encoder {
+    /// Creates an encoder using given [settings](Settings). 
+    pub fn new(settings: Settings) -> Encoder {
+        Encoder {
+            stream: Stream::new(settings, settings.alphabet), 
+            buffer: Vec::new(), 
+        }
+    }
+
+    /// Encodes a chunk of data. 
+    pub fn push(&mut self, data: &[u8]) {
+        let Ok(()) = self.stream.push(data, &mut self.buffer);
+    }
+
+    /// Encodes the checksum, should there be one, and returns the encoded string. 
+    pub fn finish(mut self) -> String {
+        let Ok(()) = self.stream.finish(&mut self.buffer);
+        into_string(self.buffer)
+    }
+}
+
+/// Adapter encoding the bytes written to it using given [settings](Settings), and writing the encoded string
+/// to an inner [`io::Write`]. 
+/// 
+/// This lets encoding act as a transparent filter in existing [`io::Write`] pipelines, e.g. with
+/// [`io::copy`]; unlike [`encode_to_writer`], the data needn't be known up front. The encoding continues
+/// across writes, so the result is the same as encoding all data written at once. The checksum can only be
+/// encoded once all data is written, so it's written by [`EncodeWriter::finish`], which must be called to
+/// complete the encoded string; [flushing](io::Write::flush) only flushes the inner writer. Since many small
+/// writes are made to the inner writer, wrapping unbuffered writers in a [`BufWriter`](io::BufWriter) is
+/// recommended. 
+/// 
+/// Should writing to the inner writer fail, the data passed to that write may have been partially encoded, 
+/// and the encoded string can't be completed. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use std::io::Write;
+/// use bunk::{EncodeWriter, Settings};
+/// 
+/// let mut writer = EncodeWriter::new(Vec::new(), Settings::default());
+/// writer.write_all(b"after")?;
+/// writer.write_all(b"sun")?;
+/// let output = writer.finish()?;
+/// 
+/// assert_eq!(output, bunk::encode(b"aftersun").as_bytes());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct EncodeWriter<W> {
+    /// State of the encoding. 
+    stream: Stream, 
+    /// Writer the encoded string is written to. 
+    writer: W, 
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> EncodeWriter<W> {
+    /// Creates an adapter writing the encoded string to given writer using given [settings](Settings). 
+    pub fn new(writer: W, settings: Settings) -> EncodeWriter<W> {
+        EncodeWriter {
+            stream: Stream::new(settings, settings.alphabet), 
+            writer, 
+        }
+    }
+
+    /// Returns a reference to the inner writer. 
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the inner writer. Writing to it directly corrupts the encoded string. 
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Encodes the checksum, should there be one, flushes the inner writer, and returns it. 
+    pub fn finish(self) -> io::Result<W> {
+        let EncodeWriter { stream, mut writer } = self;
+        stream.finish(&mut IoOutput(&mut writer))?;
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> io::Write for EncodeWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.stream.push(data, &mut IoOutput(&mut self.writer))?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Iterator of the tokens of an encoded string. See [`encode_iter`]. 
+struct Tokens<D> {
+    /// Data being encoded. 
+    data: D, 
+    /// State of the encoding. The index of the stream is the index of the next payload byte. 
+    stream: Stream, 
+    /// Checksum of a chunk being encoded: the hash used as seed for the sentence, the checksum bytes, the
+    /// number of them, and the number of them encoded so far. 
+    checksum: Option<(Fnv1a, [u8; 8], usize, usize)>, 
+    /// Syllable following a yielded word-break. 
+    syllable: Option<&'static [u8]>, 
+    /// Whether the payload has been encoded and the checksum of the last chunk begun. 
+    finished: bool, 
+    /// Whether the final token has been yielded. 
+    done: bool, 
+}
+
+impl<D: AsRef<[u8]>> Iterator for Tokens<D> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if let Some(syllable) = self.syllable.take() {
+            return Some(Token::Syllable(syllable))
+        }
+        let stream = &mut self.stream;
+        let (checksum, order) = (stream.checksum, stream.checksum_order);
+        let begin = move |(hash, len): (Fnv1a, usize)| (hash, hash.bytes(checksum, order), len, 0);
+
+        let (byte, seed) = loop {
+            if let Some((hash, bytes, len, encoded)) = &mut self.checksum {
+                if *encoded < *len {
+                    if *encoded == 0 {
+                        stream.sentence.begin_checksum();
+                    }
+                    let byte = bytes[*encoded];
+                    *encoded += 1;
+                    // the hash is updated here only to be used as seed for the sentence
+                    hash.update(byte);
+                    break (byte, *hash)
+                }
+                self.checksum = None;
+            }
+            match self.data.as_ref().get(stream.index) {
+                Some(&byte) => {
+                    let byte = stream.code(byte);
+                    let seed = stream.hash;
+                    self.checksum = stream.end_chunk().map(begin);
+                    break (byte, seed)
+                }
+                None if !self.finished => {
+                    self.finished = true;
+                    self.checksum = stream.last_chunk().map(begin);
+                }
+                None => {
+                    // end the final sentence with a period when decorating
+                    let sentence = &stream.sentence;
+                    let period = sentence.decoration.is_some() && sentence.started && !self.done;
+                    self.done = true;
+                    return period.then_some(Token::Break(BreakKind::Period))
+                }
+            }
+        };
+        let syllable = syllables::get(stream.sentence.table, byte);
+
+        match stream.sentence.step(byte, seed) {
+            Some(word_break) => {
+                self.syllable = Some(syllable);
+                Some(Token::Break(word_break))
+            }
+            None => Some(Token::Syllable(syllable)), 
+        }
+    }
+}
+
+/// Destination of the text produced when encoding. 
+trait Output {
+    /// Error produced when writing fails. 
+    type Error;
+
+    /// Writes a UTF-8 encoded string. 
+    fn write(&mut self, text: &[u8]) -> core::result::Result<(), Self::Error>;
+}
+
+impl Output for Vec<u8> {
+    type Error = Infallible;
+
+    fn write(&mut self, text: &[u8]) -> core::result::Result<(), Infallible> {
+        self.extend_from_slice(text);
+        Ok(())
+    }
+}
+
+/// Adapter writing encoded text to an [`io::Write`]. 
+#[cfg(feature = "std")]
+struct IoOutput<'a, W>(&'a mut W);
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Output for IoOutput<'_, W> {
+    type Error = io::Error;
+
+    fn write(&mut self, text: &[u8]) -> io::Result<()> {
+        self.0.write_all(text)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> Output for heapless::String<N> {
+    type Error = CapacityError;
+
+    fn write(&mut self, text: &[u8]) -> core::result::Result<(), CapacityError> {
+        let string = core::str::from_utf8(text).expect("All syllables and delimiters are valid UTF-8");
+        self.push_str(string).map_err(|()| CapacityError)
+    }
+}
+
+/// Adapter writing encoded text to a [`fmt::Write`]. 
+struct FmtOutput<'a, W>(&'a mut W);
+
+impl<W: fmt::Write> Output for FmtOutput<'_, W> {
+    type Error = fmt::Error;
+
+    fn write(&mut self, text: &[u8]) -> fmt::Result {
+        let string = core::str::from_utf8(text).expect("All syllables and delimiters are valid UTF-8");
+        self.0.write_str(string)
+    }
+}
+
+/// Encodes data fed to it in chunks into an [`Output`]. 
+/// 
+/// Performs the [`running_code`] and computes the checksum; the syllables are then encoded by a
+/// [`Sentence`]. 
+#[derive(Clone, Debug)]
+struct Stream<L = Alphabet> {
+    /// Sentence encoding the syllables. 
+    sentence: Sentence<L>, 
+    /// Hash of the payload so far. 
+    hash: Fnv1a, 
+    /// Index of the next payload byte. Used for the [`running_code`]. 
+    index: usize, 
+    /// The checksum settings used. 
+    checksum: Checksum, 
+    /// Seed mixed into the checksum. 
+    checksum_seed: u32, 
+    /// Byte order of the checksum. 
+    checksum_order: Endianness, 
+    /// Number of payload bytes covered by each checksum, or `0` for a single checksum. 
+    checksum_chunk: usize, 
+    /// Whether the [`running_code`] is performed. 
+    smoothing: bool, 
+    /// Seed used for the [`running_code`]. 
+    seed: u64, 
+    /// Key of the [`whiten`] pass, if any. 
+    whitening: Option<u64>, 
+}
+
+impl<L: Lookup> Stream<L> {
+    /// Creates a stream using given [settings](Settings) and syllable table. 
+    fn new(settings: Settings, table: L) -> Stream<L> {
+        let Settings{
+            word_len: max_word, 
+            min_word_len: min_word, 
+            grouping, 
+            separator, 
+            pad_syllables, 
+            minimal_breaks, 
+            checksum, 
+            checksum_seed, 
+            checksum_order, 
+            checksum_chunk, 
+            decorate, 
+            decoration, 
+            uppercase, 
+            smoothing, 
+            seed, 
+            whitening, 
+            alphabet: _, 
+        } = settings;
+        let sentence = Sentence {
+            started: false, 
+            words: 0, 
+            previous: None, 
+            word_len: 0, 
+            max_word: max_word.filter(|&max| max != 0).map_or(usize::MAX, usize::from), 
+            min_word: min_word.map_or(0, usize::from), 
+            grouping, 
+            separator, 
+            pad_syllables, 
+            minimal_breaks, 
+            syllables: 0, 
+            decoration: decorate.then_some(decoration), 
+            uppercase, 
+            checksum_next: false, 
+            table, 
+        };
+        Stream {
+            sentence, 
+            hash: Fnv1a::new_seeded(checksum_seed), 
+            index: 0, 
+            checksum, 
+            checksum_seed, 
+            checksum_order, 
+            checksum_chunk, 
+            smoothing, 
+            seed, 
+            whitening, 
+        }
+    }
+
+    /// Encodes a chunk of data. 
+    fn push<O: Output>(&mut self, data: &[u8], out: &mut O) -> core::result::Result<(), O::Error> {
+        if self.smoothing && data.len() >= RunningMask::THRESHOLD {
+            return self.push_masked(data, out)
+        }
+        for &byte in data {
+            let encoded = self.code(byte);
+            self.sentence.push(encoded, self.hash, out)?;
+
+            if let Some(ended) = self.end_chunk() {
+                self.push_checksum(ended, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes a large chunk of data, performing the [`running_code`] with a precomputed [`RunningMask`]. 
+    fn push_masked<O: Output>(&mut self, data: &[u8], out: &mut O) -> core::result::Result<(), O::Error> {
+        let mask = RunningMask::new(self.index, self.seed);
+        let mut block = [0; 256];
+
+        for chunk in data.chunks(block.len()) {
+            let block = &mut block[..chunk.len()];
+            block.copy_from_slice(chunk);
+            mask.apply(block);
+
+            if let Some(key) = self.whitening {
+                whiten_slice(block, self.index, key);
+            }
+
+            for (&byte, &encoded) in core::iter::zip(chunk, &*block) {
+                self.hash.update(byte);
+                self.index += 1;
+                self.sentence.push(encoded, self.hash, out)?;
+
+                if let Some(ended) = self.end_chunk() {
+                    self.push_checksum(ended, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Hashes a payload byte and performs the [`running_code`] and [`whiten`] pass on it, should they be
+    /// enabled. 
+    fn code(&mut self, byte: u8) -> u8 {
+        self.hash.update(byte);
+        let smoothed = match self.smoothing {
+            true => running_code(byte, self.index, self.seed), 
+            false => byte, 
+        };
+        let encoded = match self.whitening {
+            Some(key) => whiten(smoothed, self.index, key), 
+            None => smoothed, 
+        };
+        self.index += 1;
+        encoded
+    }
+
+    /// Encodes the checksum of the last chunk, should there be one, and performs final decorations. 
+    fn finish<O: Output>(mut self, out: &mut O) -> core::result::Result<(), O::Error> {
+        if let Some(last) = self.last_chunk() {
+            self.push_checksum(last, out)?;
+        }
+        self.sentence.finalise(out)
+    }
+
+    /// Resets the hash for the next [chunk](Settings::checksum_chunk), should the last payload byte have
+    /// completed one. 
+    /// 
+    /// Returns the hash of the completed chunk and the length of its checksum. 
+    fn end_chunk(&mut self) -> Option<(Fnv1a, usize)> {
+        let chunk_len = self.checksum_chunk;
+
+        if chunk_len == 0 || !self.index.is_multiple_of(chunk_len) {
+            return None
+        }
+        let seed = self.checksum_seed.wrapping_add((self.index / chunk_len) as u32);
+        let hash = mem::replace(&mut self.hash, Fnv1a::new_seeded(seed));
+        Some((hash, self.checksum.len_for(chunk_len)))
+    }
+
+    /// Returns the hash of the last chunk and the length of its checksum, unless the last payload byte
+    /// completed a [chunk](Settings::checksum_chunk) whose checksum has already been encoded. Without chunks,
+    /// this is the checksum of all data. 
+    fn last_chunk(&self) -> Option<(Fnv1a, usize)> {
+        let rest = match self.checksum_chunk {
+            0 => self.index, 
+            chunk_len => self.index % chunk_len, 
+        };
+        (rest != 0 || self.index == 0).then(|| (self.hash, self.checksum.len_for(rest)))
+    }
+
+    /// Encodes the checksum of a chunk given its hash and the length of the checksum. 
+    fn push_checksum<O: Output>(&mut self, chunk: (Fnv1a, usize), out: &mut O)
+        -> core::result::Result<(), O::Error>
+    {
+        let (mut hash, checksum_len) = chunk;
+        let checksum_bytes = hash.bytes(self.checksum, self.checksum_order);
+
+        if checksum_len != 0 {
+            self.sentence.begin_checksum();
+        }
+        for &byte in &checksum_bytes[..checksum_len] {
+            // the hash is updated here only to be used as seed for the sentence encoder
+            hash.update(byte);
+            self.sentence.push(byte, hash, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes bytes as a string of syllables one-by-one. 
+/// 
+/// Does not perform the [`running_code`] or compute a checksum; that is handled in [`Stream`]. 
+#[derive(Clone, Debug)]
+struct Sentence<L = Alphabet> {
+    /// Whether any syllables have been encoded yet. 
+    started: bool, 
+    /// Number of word-breaks so far. 
+    words: usize, 
+    /// Index of the previous syllable. Used for detecting ambiguity. 
+    previous: Option<u8>, 
+    /// Current word length in syllables. Not bounded by the maximum when words are only broken to avoid
+    /// ambiguity, so this is wider than the settings. 
+    word_len: usize, 
+    /// Maximum allowed word length in syllables, or [`usize::MAX`] if words aren't broken due to their
+    /// length. 
+    max_word: usize, 
+    /// Minimum word length in syllables before a word may be broken due to its length. 
+    min_word: usize, 
+    /// How syllables are grouped into words. 
+    grouping: WordGrouping, 
+    /// Character separating words. 
+    separator: char, 
+    /// Character padding syllables to the maximum syllable length, if any. 
+    pad_syllables: Option<char>, 
+    /// Whether word-breaks are only inserted where needed for the decoder to tell syllables apart. 
+    minimal_breaks: bool, 
+    /// Number of syllables so far. 
+    syllables: usize, 
+    /// The decorations used, if the sentence should be decorated with periods, commas, and sentence casing. 
+    decoration: Option<Decoration>, 
+    /// Whether all letters are uppercased. 
+    uppercase: bool, 
+    /// Whether the next syllable is the first of the checksum, to be preceded by the checksum delimiter. 
+    checksum_next: bool, 
+    /// The syllable table used. 
+    table: L, 
+}
+
+impl<L: Lookup> Sentence<L> {
+    /// Encodes a single byte. The seed is used to inform whether a word-break space should be replaced with
+    /// a comma or period. 
+    fn push<O: Output>(&mut self, byte: u8, seed: Fnv1a, out: &mut O) -> core::result::Result<(), O::Error> {
+        let first = !self.started;
+        let word_break = self.step(byte, seed);
+        let syllable = self.table.get(byte);
+
+        // if we're decorating, use the period or comma as per the decoration, else just use the separator
+        let mut separator = [0; 4];
+        let delim: Option<&[u8]> = match (word_break, self.decoration) {
+            (Some(BreakKind::Period), Some(decoration)) => Some(decoration.period.as_bytes()), 
+            (Some(BreakKind::Comma), Some(decoration))  => Some(decoration.comma.as_bytes()), 
+            (Some(BreakKind::Checksum), Some(decoration)) => decoration.checksum.map(str::as_bytes), 
+            (Some(_), _) => Some(self.separator.encode_utf8(&mut separator).as_bytes()), 
+            (None, _)    => None, 
+        };
+        let capitalise = self.decoration.is_some() && (first || word_break == Some(BreakKind::Period));
+
+        if let Some(delim) = delim {
+            out.write(delim)?;
+        }
+        match (self.uppercase, capitalise) {
+            (true, _) => for chunk in syllable.chunks(syllables::MAX_LEN) {
+                let mut upper = [0; syllables::MAX_LEN];
+                let upper = &mut upper[..chunk.len()];
+                upper.copy_from_slice(chunk);
+                upper.make_ascii_uppercase();
+                out.write(upper)?;
+            }
+            (false, true) => {
+                out.write(&[syllable[0].to_ascii_uppercase()])?;
+                out.write(&syllable[1..])?;
+            }
+            (false, false) => out.write(syllable)?, 
+        }
+        if let Some(pad) = self.pad_syllables {
+            let mut padding = [0; 4];
+            let padding = pad.encode_utf8(&mut padding).as_bytes();
+
+            for _ in syllable.len()..syllables::MAX_LEN {
+                out.write(padding)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Advances the sentence by a single byte without writing anything. 
+    /// 
+    /// Returns the word-break to be inserted before the syllable of the byte, if any. 
+    fn step(&mut self, byte: u8, seed: Fnv1a) -> Option<BreakKind> {
+        // get syllable corresponding to byte and determine whether we need a word-break before we add it
+        let syllable = self.table.get(byte);
+        let ambiguous = |preceding| {
+            let preceding = self.table.get(preceding);
+
+            match self.minimal_breaks {
+                // there is a parsing ambiguity if the decoder could match a longer syllable, even after
+                // backtracking
+                true => self.table.needs_break(preceding, syllable), 
+                // there is a parsing ambiguity if the first char of the next syllable is a valid continuation
+                // of the previous syllable
+                false => self.table.char_follows(syllable[0], preceding), 
+            }
+        }; 
+        let too_long = match self.grouping {
+            WordGrouping::Variable => {
+                self.started && self.word_len >= self.max_word && self.word_len >= self.min_word
+            }
+            WordGrouping::Fixed(n) => {
+                n != 0 && self.syllables != 0 && self.syllables.is_multiple_of(n as usize)
+            }
+        };
+        let word_break = (too_long || self.previous.is_some_and(ambiguous)).then(|| match self.decoration {
+            // if we're decorating, replace a word-break space with a period or comma as per the mode
+            Some(decoration) => self.punctuation(decoration, seed), 
+            None => BreakKind::Space, 
+        });
+        // the checksum delimiter takes the place of any other word-break
+        let word_break = match mem::take(&mut self.checksum_next) {
+            true => Some(BreakKind::Checksum), 
+            false => word_break, 
+        };
+
+        // if there's a word-break, reset ambiguity control vars
+        if word_break.is_some() {
+            self.words += 1;
+            self.word_len = 0;
+            self.previous = None;
+        }
+        self.started = true;
+        self.previous = Some(byte);
+        self.word_len += 1;
+        self.syllables += 1;
+        word_break
+    }
+
+    /// Marks the next syllable as the first of the checksum, so that it's preceded by the
+    /// [checksum delimiter](Decoration::checksum), should there be one. 
+    fn begin_checksum(&mut self) {
+        let delimited = self.decoration.is_some_and(|decoration| decoration.checksum.is_some());
+        self.checksum_next = self.started && delimited;
+    }
+
+    /// Determines the punctuation to replace a word-break with when decorating. 
+    fn punctuation(&self, decoration: Decoration, seed: Fnv1a) -> BreakKind {
+        // number of words preceding the word-break
+        let words = self.words + 1;
+        let nth = |n: u8| n != 0 && words.is_multiple_of(n as usize);
+
+        match decoration.mode {
+            DecorationMode::Hashed => {
+                let seed = seed.hash32.count_ones();
+
+                if seed > decoration.period_threshold {
+                    BreakKind::Period
+                } else if seed < decoration.comma_threshold {
+                    BreakKind::Comma
+                } else {
+                    BreakKind::Space
+                }
+            }
+            DecorationMode::Periodic{ sentence_len, .. } if nth(sentence_len) => BreakKind::Period, 
+            DecorationMode::Periodic{ clause_len, .. } if nth(clause_len) => BreakKind::Comma, 
+            DecorationMode::Periodic{ .. } => BreakKind::Space, 
+        }
+    }
+
+    /// Performs final decorations, should there be any. 
+    fn finalise<O: Output>(self, out: &mut O) -> core::result::Result<(), O::Error> {
+        match self.decoration {
+            Some(decoration) if self.started => out.write(decoration.period.trim_end().as_bytes()), 
+            _ => Ok(()), 
+        }
+    }
+}
+
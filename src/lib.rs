@@ -56,18 +56,70 @@
 //! }
 //! ```
 //! 
-//! Note that the [settings](Settings) used when encoding for serde are necessarily hard-coded: 
+//! Data is only encoded for human-readable formats such as JSON or TOML; binary formats such as bincode get
+//! the raw bytes. 
+//! 
+//! The [settings](Settings) used by `#[serde(with = "bunk")]` are hard-coded as: 
 //! ```no_run
 //! # use bunk::*;
 //! # let _ =
 //! Settings {
 //!     word_len: Some(3), 
+//!     min_word_len: None, 
+//!     grouping: WordGrouping::Variable, 
+//!     separator: ' ', 
+//!     pad_syllables: None, 
+//!     minimal_breaks: false, 
 //!     checksum: Checksum::Disabled, 
+//!     checksum_seed: 0, 
+//!     checksum_order: Endianness::Little, 
+//!     checksum_chunk: 0, 
 //!     decorate: false, 
+//!     decoration: Decoration::default(), 
+//!     uppercase: false, 
+//!     smoothing: true, 
+//!     seed: 0, 
+//!     whitening: None, 
+//!     alphabet: Alphabet::Standard, 
 //! }
 //! # ;
 //! ```
 //! 
+//! These are also available as [`SERDE_SETTINGS`], for encoding and decoding outside of Serde. 
+//! 
+//! To use other settings, generate a module with [`serde_with`] and pass its path to `#[serde(with)]`: 
+//! ```text
+//! bunk::serde_with!(mod checked, Settings {
+//!     checksum: Checksum::Length2, 
+//!     ..Default::default()
+//! });
+//! 
+//! #[derive(Serialize, Deserialize)]
+//! struct Vault {
+//!     #[serde(with = "checked")]
+//!     key: Vec<u8>, 
+//!     name: String, 
+//! }
+//! ```
+//! 
+//! 
+//! # Alphabets
+//! 
+//! Enable the `soft-table` feature for an additional [alphabet](Alphabet) of softer syllables, mostly
+//! consisting of a consonant followed by a vowel, or the `compact-table` feature for an alphabet of
+//! letters and digits yielding shorter encoded strings. The alphabet is selected with
+//! [`Settings::alphabet`], and must match when decoding. 
+//! 
+//! 
+//! # `no_std`
+//! 
+//! Bunk supports `no_std` environments with an allocator. Disable the default `std` feature to use it
+//! without the standard library; this removes only the functionality relying on [`std::io`]. Use
+//! [`encode_to_fmt`] in place of `encode_to_writer` to write into any [`core::fmt::Write`]. 
+//! 
+//! To encode without touching the heap, enable the `heapless` feature and use `encode_heapless` to encode
+//! into a fixed-capacity string. Note that an allocator is still required, since the crate links `alloc`. 
+//! 
 //! 
 //! # Examples
 //! 
@@ -141,7 +193,12 @@
 //! therefore has to detect when such an ambiguity is possible by checking if the first letter of the second
 //! syllable is a valid continuation of the first syllable. If so, it inserts a word break between them.
 //! (Technically, this is stricter than necessary for breaking the ambiguity but is easy to check and allows
-//! the decoder to be written greedily.)
+//! the decoder to be written greedily. [Minimal breaks](Settings::minimal_breaks) relax this at the cost of
+//! a backtracking decoder.)
+//! 
+//! The word breaks are thus part of the encoding, and strings with their whitespace stripped can't always be
+//! decoded. For transports that do so, [`encode_delimiterless`] instead marks word breaks by capitalizing
+//! each word. 
 //! 
 //! To support these two required operations --- finding the longest syllable prefixed to a string, and
 //! checking whether a letter is a valid continuation of a syllable --- Bunk uses a trie. There are then two
@@ -161,19 +218,49 @@
 //! value dependent on their index. Since XOR undoes itself, the decoder can then do the exact same thing and
 //! retrieve the original bytes. With this in place, `[0, 0, 0, 0]` gets nicely encoded as "trirori mulry". 
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 mod encode;
 mod decode;
+mod string;
+mod constant;
 mod syllables;
+mod table;
 mod serde;
+mod wasm;
+mod clap;
+mod testing;
 
 pub use encode::*;
 pub use decode::*;
+pub use string::*;
+pub use constant::*;
+pub use table::*;
 
 #[cfg(feature = "serde")]
 pub use serde::*;
 
+#[cfg(feature = "clap")]
+pub use clap::*;
+
+#[cfg(any(feature = "testing", test))]
+pub use testing::*;
+
+/// Re-exports used by macros. Not public API. 
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub mod __private {
+    pub use ::serde;
+    pub use alloc::vec::Vec;
+}
+
 /// Specifies the number of checksum bytes used when encoding. 
 /// 
+/// Checksums of up to four bytes are computed with the 32-bit variant of the FNV-1a hashing algorithm, and
+/// longer checksums with the 64-bit variant. 
+/// 
 /// Default: [`Checksum::Length1`]. 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Checksum {
@@ -187,12 +274,182 @@ pub enum Checksum {
     Length3, 
     /// Four bytes used. 
     Length4, 
+    /// Five bytes used. 
+    Length5, 
+    /// Six bytes used. 
+    Length6, 
+    /// Seven bytes used. 
+    Length7, 
+    /// Eight bytes used. 
+    Length8, 
+    /// Number of bytes scaling with the length of the payload: one byte per 16 payload bytes started, up to
+    /// four bytes. Payloads of up to 15 bytes thus get one byte, and payloads of 48 bytes or more get four.
+    /// See [`Checksum::len_for`]. 
+    /// 
+    /// The decoder recovers the payload length from the number of syllables, as
+    /// [`Checksum::payload_len`]; a syllable count that no payload length yields is reported as
+    /// [`InvalidData::Checksum`]. 
+    Auto, 
 }
 
 impl Checksum {
-    /// Returns the number of checksum bytes to be included in encoded data. 
-    const fn len(self) -> usize {
-        self as usize
+    /// Returns the number of checksum bytes to be included in encoded data. For [`Checksum::Auto`], this is
+    /// the maximum of four bytes; use [`Checksum::len_for`] to get the number for a given payload. 
+    /// 
+    /// For all but [`Checksum::Auto`], this is the inverse of [`Checksum::from_len`]. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Checksum;
+    /// 
+    /// assert_eq!(Checksum::Disabled.len(), 0);
+    /// assert_eq!(Checksum::Length3.len(), 3);
+    /// assert_eq!(Checksum::Auto.len(), 4);
+    /// assert_eq!(Checksum::from_len(Checksum::Length3.len()), Some(Checksum::Length3));
+    /// assert_eq!(Checksum::from_len(Checksum::Auto.len()), Some(Checksum::Length4));
+    /// ```
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(self) -> usize {
+        match self {
+            Checksum::Disabled => 0, 
+            Checksum::Length1 => 1, 
+            Checksum::Length2 => 2, 
+            Checksum::Length3 => 3, 
+            Checksum::Length4 => 4, 
+            Checksum::Length5 => 5, 
+            Checksum::Length6 => 6, 
+            Checksum::Length7 => 7, 
+            Checksum::Length8 => 8, 
+            Checksum::Auto => 4, 
+        }
+    }
+
+    /// Returns the number of checksum bytes to be included in encoded data with a payload of given length. 
+    /// This only differs from [`Checksum::len`] for [`Checksum::Auto`]. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Checksum;
+    /// 
+    /// assert_eq!(Checksum::Length3.len_for(100), 3);
+    /// assert_eq!(Checksum::Auto.len_for(0), 1);
+    /// assert_eq!(Checksum::Auto.len_for(15), 1);
+    /// assert_eq!(Checksum::Auto.len_for(16), 2);
+    /// assert_eq!(Checksum::Auto.len_for(48), 4);
+    /// assert_eq!(Checksum::Auto.len_for(1000), 4);
+    /// ```
+    pub const fn len_for(self, payload_len: usize) -> usize {
+        match self {
+            Checksum::Auto if payload_len < 48 => 1 + payload_len / 16, 
+            checksum => checksum.len(), 
+        }
+    }
+
+    /// Returns the length of the payload in encoded data of given total length in bytes (i.e., syllables), 
+    /// including the checksum. Returns `None` if no payload length yields that total. The inverse of adding
+    /// [`Checksum::len_for`] to the payload length. 
+    /// 
+    /// Since the total length grows by one or two bytes with each payload byte, this is always unambiguous.
+    /// For [`Checksum::Auto`], a payload of `16 * q + r` bytes with `r < 16` and `q < 3` has a total of
+    /// `17 * q + r + 1` bytes, so a total is skipped whenever the checksum grows; longer payloads simply
+    /// have four bytes added. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Checksum;
+    /// 
+    /// assert_eq!(Checksum::Length2.payload_len(10), Some(8));
+    /// assert_eq!(Checksum::Length2.payload_len(1), None);
+    /// assert_eq!(Checksum::Auto.payload_len(16), Some(15)); // 15 + 1
+    /// assert_eq!(Checksum::Auto.payload_len(17), None);
+    /// assert_eq!(Checksum::Auto.payload_len(18), Some(16)); // 16 + 2
+    /// assert_eq!(Checksum::Auto.payload_len(100), Some(96)); // 96 + 4
+    /// ```
+    pub const fn payload_len(self, total_len: usize) -> Option<usize> {
+        match self {
+            Checksum::Auto if total_len < 52 => {
+                // total_len - 1 = 17 * q + r, with a remainder r of at most 15
+                let (q, r) = match total_len.checked_sub(1) {
+                    Some(len) => (len / 17, len % 17), 
+                    None => return None, 
+                };
+                match r {
+                    16 => None, 
+                    _ => Some(16 * q + r), 
+                }
+            }
+            checksum => total_len.checked_sub(checksum.len()), 
+        }
+    }
+
+    /// Returns the total length in bytes of encoded data with a payload of given length, with a checksum
+    /// after every [chunk](Settings::checksum_chunk) of `chunk_len` bytes, or only after all of it if
+    /// `chunk_len` is `0`. Saturates at [`usize::MAX`]. 
+    const fn chunked_len(self, payload_len: usize, chunk_len: usize) -> usize {
+        if chunk_len == 0 {
+            return payload_len.saturating_add(self.len_for(payload_len))
+        }
+        let (chunks, rest) = (payload_len / chunk_len, payload_len % chunk_len);
+
+        // the last chunk is only empty for empty data
+        let last_len = match chunks == 0 || rest != 0 {
+            true => rest + self.len_for(rest), 
+            false => 0, 
+        };
+        chunks
+            .saturating_mul(chunk_len.saturating_add(self.len_for(chunk_len)))
+            .saturating_add(last_len)
+    }
+
+    /// Returns the length of the payload in encoded data of given total length in bytes, with a checksum
+    /// after every [chunk](Settings::checksum_chunk) of `chunk_len` bytes. Returns `None` if no payload
+    /// length yields that total. The inverse of [`Checksum::chunked_len`]. 
+    const fn chunked_payload_len(self, total_len: usize, chunk_len: usize) -> Option<usize> {
+        if chunk_len == 0 {
+            return self.payload_len(total_len)
+        }
+        let frame_len = chunk_len.saturating_add(self.len_for(chunk_len));
+        let (chunks, rest) = (total_len / frame_len, total_len % frame_len);
+
+        // the rest is shorter than a full chunk with its checksum, so its payload is shorter than a chunk
+        match (chunks, rest, self.payload_len(rest)) {
+            (_, 0, _) if chunks != 0 => Some(chunks * chunk_len), 
+            (0, _, payload_len) => payload_len, 
+            (_, _, Some(payload_len)) if payload_len != 0 => Some(chunks * chunk_len + payload_len), 
+            _ => None, 
+        }
+    }
+
+    /// Gets the checksum settings using a given number of bytes. Returns `None` if there are none using that
+    /// many bytes. Only fixed lengths are returned, never [`Checksum::Auto`], so this is the inverse of
+    /// [`Checksum::len`] for all but [`Checksum::Auto`]. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Checksum;
+    /// 
+    /// assert_eq!(Checksum::from_len(0), Some(Checksum::Disabled));
+    /// assert_eq!(Checksum::from_len(3), Some(Checksum::Length3));
+    /// assert_eq!(Checksum::from_len(9), None);
+    /// assert_ne!(Checksum::from_len(Checksum::Auto.len()), Some(Checksum::Auto));
+    /// ```
+    pub const fn from_len(len: usize) -> Option<Checksum> {
+        match len {
+            0 => Some(Checksum::Disabled), 
+            1 => Some(Checksum::Length1), 
+            2 => Some(Checksum::Length2), 
+            3 => Some(Checksum::Length3), 
+            4 => Some(Checksum::Length4), 
+            5 => Some(Checksum::Length5), 
+            6 => Some(Checksum::Length6), 
+            7 => Some(Checksum::Length7), 
+            8 => Some(Checksum::Length8), 
+            _ => None, 
+        }
     }
 }
 
@@ -202,52 +459,488 @@ impl Default for Checksum {
     }
 }
 
+/// Specifies the byte order of the [checksum](Settings::checksum_order). 
+/// 
+/// The checksum is the FNV-1a hash of the payload, converted to bytes in this order and truncated to the
+/// [checksum length](Checksum::len_for). With big-endian byte order, a checksum shorter than the hash thus
+/// consists of its most significant bytes. This is an interoperability setting for other implementations of
+/// the encoding; there's no reason to change it otherwise. 
+/// 
+/// Default: [`Endianness::Little`]. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, DecodeSettings, Endianness, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Length2, 
+///     checksum_order: Endianness::Big, 
+///     ..Default::default()
+/// };
+/// let encoded = bunk::encode_with_settings(b"aftersun", settings);
+/// 
+/// assert_eq!(bunk::decode_with_settings(&encoded, settings)?, b"aftersun");
+/// assert!(bunk::decode_with_settings(&encoded, Checksum::Length2).is_err());
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first. 
+    Little, 
+    /// Most significant byte first. 
+    Big, 
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Endianness::Little
+    }
+}
+
+/// Specifies the built-in table of syllables used when encoding and decoding. 
+/// 
+/// Strings must be decoded using the same alphabet they were encoded with. 
+/// 
+/// Default: [`Alphabet::Standard`]. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The standard syllables, somewhat resembling Latin. 
+    Standard, 
+    /// Softer syllables, mostly consisting of a consonant followed by a vowel, for a more pronounceable
+    /// result. Requires the `soft-table` feature. 
+    #[cfg(feature = "soft-table")]
+    Soft, 
+    /// Syllables of one or two lowercase letters and digits, for shorter encoded strings when
+    /// pronounceability is less important. No syllable is a prefix of another, so word-breaks are only
+    /// inserted due to [word length](Settings::word_len). Since digits are part of the syllables, they must
+    /// not be used in [decorations](Decoration). Requires the `compact-table` feature. 
+    #[cfg(feature = "compact-table")]
+    Compact, 
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::Standard
+    }
+}
+
+/// Gets the syllable of the [standard alphabet](Alphabet::Standard) encoding a byte. 
+/// 
+/// Note that with [smoothing](Settings::smoothing) enabled, bytes are transformed before getting their
+/// syllables, so the syllables in an encoded string generally don't correspond directly to the input bytes.
+/// All syllables are between 1-4 lowercase ascii letters. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// assert_eq!(bunk::syllable(0), "u");
+/// assert!((0..=u8::MAX).all(|byte| (1..=4).contains(&bunk::syllable(byte).len())));
+/// ```
+pub fn syllable(byte: u8) -> &'static str {
+    syllables::get_str(Alphabet::Standard, byte)
+}
+
+/// Iterates over every syllable of the [standard alphabet](Alphabet::Standard), paired with the byte it
+/// encodes, in order of the bytes. 
+/// 
+/// This is useful for e.g. documenting the table or analysing syllable frequencies; see [`syllable`] for
+/// caveats. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// let total_len: usize = bunk::syllables().map(|(_, syllable)| syllable.len()).sum();
+/// 
+/// assert_eq!(bunk::syllables().count(), 256);
+/// assert_eq!(bunk::syllables().next(), Some((0, "u")));
+/// assert_eq!(total_len, 632); // an average of 2.47 letters
+/// ```
+pub fn syllables() -> impl Iterator<Item = (u8, &'static str)> {
+    (0..=u8::MAX).map(|byte| (byte, syllable(byte)))
+}
+
+/// Gets the byte encoded by a syllable of the [standard alphabet](Alphabet::Standard); the inverse of
+/// [`syllable`]. 
+/// 
+/// The whole string must be exactly one syllable, ignoring case. Returns `None` for unrecognized syllables,
+/// and for strings that only start with or are a part of a syllable. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// assert_eq!(bunk::syllable_index("u"), Some(0));
+/// assert_eq!(bunk::syllable_index("U"), Some(0));
+/// assert_eq!(bunk::syllable_index("uu"), None);
+/// assert!((0..=u8::MAX).all(|byte| bunk::syllable_index(bunk::syllable(byte)) == Some(byte)));
+/// ```
+pub fn syllable_index(string: &str) -> Option<u8> {
+    syllables::longest_prefix_of(Alphabet::Standard, string)
+        .filter(|&(_, len)| len == string.len())
+        .map(|(index, _)| index)
+}
+
+/// Determines whether the encoder inserts a word-break between a syllable of the
+/// [standard alphabet](Alphabet::Standard) and the syllable encoding a byte, to avoid a parsing ambiguity. 
+/// 
+/// A word-break is needed if the first letter of the next syllable is a valid continuation of the previous
+/// syllable, since the decoder would otherwise greedily parse a longer syllable. Other word-breaks, e.g. due
+/// to [`Settings::word_len`], are not considered. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// assert_eq!(bunk::syllable(10), "sa");
+/// assert!(bunk::would_break("u", 10)); // "us" is also a syllable
+/// assert!(!bunk::would_break("u", 0));
+/// ```
+pub fn would_break(prev_syllable: &str, next_byte: u8) -> bool {
+    let next = syllables::get(Alphabet::Standard, next_byte);
+    syllables::char_follows(Alphabet::Standard, next[0], prev_syllable.as_bytes())
+}
+
+/// Determines whether data survives being encoded and then decoded using given [settings](Settings). 
+/// 
+/// This is primarily a debugging aid, e.g. for checking that a combination of settings is sound, or for
+/// asserting that a recovery code is actually recoverable before displaying it. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// assert!(bunk::roundtrip(b"aftersun", Settings::default()));
+/// ```
+pub fn roundtrip(data: impl AsRef<[u8]>, settings: Settings) -> bool {
+    let data = data.as_ref();
+    let encoded = encode_with_settings(data, settings);
+    decode_with_settings(encoded, settings).is_ok_and(|decoded| decoded == data)
+}
+
+/// Determines whether a string is exactly what data encodes to using given [settings](Settings). 
+/// 
+/// This is stricter than decoding the string and comparing the data, since the decoder ignores the
+/// decoration, case and word-breaks; these must match as well. It's useful for golden tests, or for
+/// checking whether two combinations of settings yield the same canonical string. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::Settings;
+/// 
+/// let settings = Settings::default();
+/// 
+/// assert!(bunk::encodes_to(b"aftersun", "nargepa caldytis progoper", settings));
+/// assert!(!bunk::encodes_to(b"aftersun", "nargepa caldytisprogoper", settings)); // still decodes
+/// ```
+pub fn encodes_to(data: impl AsRef<[u8]>, encoded: &str, settings: Settings) -> bool {
+    encode_with_settings(data, settings) == encoded
+}
+
+/// Re-encodes an encoded string using given [settings](Settings), e.g. to turn on
+/// [decoration](Settings::decorate) for strings stored without it. 
+/// 
+/// The string is decoded with the checksum it was encoded with and the remaining settings in `to`, since
+/// those affecting the decoder --- the [alphabet](Settings::alphabet), [smoothing](Settings::smoothing) and
+/// [seeds](Settings::seed) --- must be the same for the payload to be preserved. The checksum is then
+/// recomputed as per `to`, which would usually use the same checksum as the string. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let plain = bunk::encode(b"it's such a beautiful day");
+/// let decorated = Settings {
+///     decorate: true, 
+///     ..Default::default()
+/// };
+/// let reformatted = bunk::reformat(&plain, Checksum::default(), decorated)?;
+/// 
+/// assert_eq!(reformatted, bunk::encode_with_settings(b"it's such a beautiful day", decorated));
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn reformat(encoded: &str, from_checksum: Checksum, to: Settings) -> Result<alloc::string::String> {
+    let settings = DecodeSettings {
+        checksum: from_checksum, 
+        ..DecodeSettings::from(to)
+    };
+    decode_with_settings(encoded, settings).map(|data| encode_with_settings(data, to))
+}
+
+/// Wraps an encoded string to lines of at most `columns` characters, for display on narrow screens or
+/// printed cards. 
+/// 
+/// Lines are only broken at spaces, which are replaced with newlines, so syllables are never split. Words
+/// longer than a line are left to overflow. Since decoding skips whitespace, the wrapped string decodes to
+/// the same data. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// let encoded = bunk::encode(b"aftersun");
+/// let wrapped = bunk::wrap(&encoded, 16);
+/// 
+/// assert_eq!(wrapped, "nargepa caldytis\nprogoper");
+/// assert_eq!(bunk::decode(wrapped)?, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub fn wrap(encoded: &str, columns: usize) -> alloc::string::String {
+    let mut wrapped = alloc::string::String::with_capacity(encoded.len());
+    let mut line_len = 0;
+
+    for word in encoded.split(' ') {
+        let word_len = word.chars().count();
+
+        if line_len != 0 {
+            match line_len + 1 + word_len > columns {
+                true => {
+                    wrapped.push('\n');
+                    line_len = 0;
+                }
+                false => {
+                    wrapped.push(' ');
+                    line_len += 1;
+                }
+            }
+        }
+        wrapped.push_str(word);
+        line_len += word_len;
+    }
+    wrapped
+}
+
 /// The FNV-1a hashing algorithm. 
 /// 
 /// Implementation based on pseudo-code on
 /// [Wikipedia](https://en.wikipedia.org/wiki/Fowler-Noll-Vo_hash_function). This is used for the checksum. 
-#[derive(Clone, Copy)]
-struct Fnv1a(u32);
+/// 
+/// Both the 32-bit and 64-bit variants are computed simultaneously. The 32-bit variant is used for checksums
+/// of up to four bytes, and the 64-bit variant for longer ones. 
+#[derive(Clone, Copy, Debug)]
+struct Fnv1a {
+    /// State of the 32-bit variant. 
+    hash32: u32, 
+    /// State of the 64-bit variant. 
+    hash64: u64, 
+}
 
 impl Fnv1a {
-    /// Creates a hasher initialised with the FNV offset basis. 
+    /// Creates a hasher initialised with the FNV offset bases. 
     const fn new() -> Fnv1a {
-        Fnv1a(0x811c9dc5)
+        Fnv1a {
+            hash32: 0x811c9dc5, 
+            hash64: 0xcbf29ce484222325, 
+        }
+    }
+
+    /// Creates a hasher with a seed mixed into the offset bases. A seed of `0` yields the same hasher as
+    /// [`Fnv1a::new`]. 
+    const fn new_seeded(seed: u32) -> Fnv1a {
+        let Fnv1a{ hash32, hash64 } = Fnv1a::new();
+        Fnv1a {
+            hash32: hash32 ^ seed, 
+            hash64: hash64 ^ seed as u64, 
+        }
     }
 
     /// Digests one byte. 
-    fn update(&mut self, byte: u8) {
-        self.0 ^= byte as u32;
-        self.0 = self.0.wrapping_mul(0x01000193);
+    const fn update(&mut self, byte: u8) {
+        self.hash32 ^= byte as u32;
+        self.hash32 = self.hash32.wrapping_mul(0x01000193);
+        self.hash64 ^= byte as u64;
+        self.hash64 = self.hash64.wrapping_mul(0x00000100000001b3);
     }
 
-    /// Returns the bytes to be used as checksum. 
-    const fn bytes(&self) -> [u8; 4] {
-        self.0.to_le_bytes()
+    /// Returns the bytes to be used as checksum in given byte order. Only the first [`Checksum::len_for`] the
+    /// payload bytes are used. 
+    const fn bytes(&self, checksum: Checksum, order: Endianness) -> [u8; 8] {
+        match (checksum.len(), order) {
+            (0..=4, Endianness::Little) => {
+                let [a, b, c, d] = self.hash32.to_le_bytes();
+                [a, b, c, d, 0, 0, 0, 0]
+            }
+            (0..=4, Endianness::Big) => {
+                let [a, b, c, d] = self.hash32.to_be_bytes();
+                [a, b, c, d, 0, 0, 0, 0]
+            }
+            (_, Endianness::Little) => self.hash64.to_le_bytes(), 
+            (_, Endianness::Big) => self.hash64.to_be_bytes(), 
+        }
     }
 }
 
+/// Table of all 8-bit values in a random order, used to increase the _apparent_ entropy of data when
+/// [smoothing](Settings::smoothing). 
+/// 
+/// This is part of the encoding format, published for reimplementations of Bunk that must match it
+/// byte-for-byte. Before being encoded, the byte at index `i` of the payload (the checksum excluded) is
+/// XORed with `ENTROPY_TABLE[(i as u8 ^ seed.rotate_right(8 * (i % 8) as u32) as u8) as usize]`, where
+/// `seed` is the [seed](Settings::seed) of the settings. Since XOR undoes itself, decoding does the same. 
+/// [Whitening](Settings::whitening), if enabled, is applied after this. 
+/// 
+/// The table was generated once as a random permutation and is fixed; it's stored in
+/// `static/entropy.txt`. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings, ENTROPY_TABLE};
+/// 
+/// let data = [0, 0, 0, 0];
+/// let smoothed: Vec<u8> = data.iter()
+///     .enumerate()
+///     .map(|(i, byte)| byte ^ ENTROPY_TABLE[i])
+///     .collect();
+/// let raw = Settings {
+///     checksum: Checksum::Disabled, 
+///     smoothing: false, 
+///     ..Default::default()
+/// };
+/// let settings = Settings {
+///     checksum: Checksum::Disabled, 
+///     ..Default::default()
+/// };
+/// 
+/// assert_eq!(bunk::encode_with_settings(smoothed, raw), bunk::encode_with_settings(data, settings));
+/// ```
+pub const ENTROPY_TABLE: [u8; 256] = include!("../static/entropy.txt");
+
 /// Increases _apparent_ entropy in input data. 
 /// 
 /// Before getting the syllable corresponding to a byte, it along with its index is run through this function
 /// to reduce visible patterns in the input data. This ensures that e.g. `[0, 0, 0, 0]` gets encoded as
 /// `trirori mul` and not `uuu u`. 
 /// 
+/// The [seed](Settings::seed) is mixed into the index one byte at a time, rotating through the bytes of the
+/// seed with the index. A seed of `0` leaves the index unchanged. 
+/// 
 /// Some notes: 
 /// - This neither increases nor decreases security; it is completely transparent, and used only to make the
 /// output look nicer. 
 /// - The transformation applied to bytes repeats every 256 indices. 
-/// - This function undoes itself if the index is the same; i.e., it both encodes and decodes bytes. 
+/// - This function undoes itself if the index and seed are the same; i.e., it both encodes and decodes
+/// bytes. 
 /// 
 /// ```ignore
 /// let input = 0xC5;
-/// let encoded = running_code(input, 0);
-/// let decoded = running_code(encoded, 0);
+/// let encoded = running_code(input, 0, 0);
+/// let decoded = running_code(encoded, 0, 0);
 /// assert_eq!(input, decoded)
 /// ```
-fn running_code(byte: u8, index: usize) -> u8 {
-    const TABLE: [u8; 256] = include!("../static/entropy.txt");
-    byte ^ TABLE[index & 0xFF]
+const fn running_code(byte: u8, index: usize, seed: u64) -> u8 {
+    let seed_byte = seed.rotate_right(8 * (index % 8) as u32) as u8;
+    byte ^ ENTROPY_TABLE[(index as u8 ^ seed_byte) as usize]
+}
+
+/// The [`running_code`] of 256 consecutive indices, precomputed as a mask to XOR with bytes. 
+/// 
+/// Since the [`running_code`] repeats every 256 indices, the mask applies to any run of bytes whose first
+/// index is the start of the mask plus a multiple of 256. This amortises the cost of the [`running_code`]
+/// over large inputs, and with the `simd` feature enabled, the mask is applied 32 bytes at a time. 
+#[derive(Clone)]
+struct RunningMask([u8; 256]);
+
+impl RunningMask {
+    /// Inputs shorter than this are coded byte-by-byte, since precomputing the mask costs about as much as
+    /// coding 256 bytes. 
+    const THRESHOLD: usize = 256;
+
+    /// Precomputes the mask of the indices starting at `start`. 
+    fn new(start: usize, seed: u64) -> RunningMask {
+        RunningMask(core::array::from_fn(|i| running_code(0, start.wrapping_add(i), seed)))
+    }
+
+    /// Performs the [`running_code`] on bytes, the first of which is at the start of the mask. 
+    fn apply(&self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(self.0.len()) {
+            xor(chunk, &self.0[..chunk.len()]);
+        }
+    }
+}
+
+/// XORs bytes with a mask of equal length. 
+#[cfg(feature = "simd")]
+fn xor(bytes: &mut [u8], mask: &[u8]) {
+    use wide::u8x32;
+    let mut chunks = bytes.chunks_exact_mut(32);
+    let mut masks = mask.chunks_exact(32);
+
+    for (chunk, mask) in core::iter::zip(&mut chunks, &mut masks) {
+        let lanes = |slice: &[u8]| u8x32::new(slice.try_into().expect("Chunks are 32 bytes"));
+        let coded = lanes(chunk) ^ lanes(mask);
+        chunk.copy_from_slice(&coded.to_array());
+    }
+    for (byte, mask) in core::iter::zip(chunks.into_remainder(), masks.remainder()) {
+        *byte ^= mask;
+    }
+}
+
+/// XORs bytes with a mask of equal length. 
+#[cfg(not(feature = "simd"))]
+fn xor(bytes: &mut [u8], mask: &[u8]) {
+    for (byte, mask) in core::iter::zip(bytes, mask) {
+        *byte ^= mask;
+    }
+}
+
+/// Maps a byte through a permutation of all byte values chosen by a key and the index of the byte, to even
+/// out the use of syllables when [whitening](Settings::whitening). Undone by [`unwhiten`]. 
+/// 
+/// The permutation XORs the byte with a key byte, multiplies it by an odd key byte, XORs its high half into
+/// its low half, and multiplies and offsets it by more key bytes, each step of which is invertible. The key
+/// bytes are drawn from the key and index mixed by SplitMix64. 
+const fn whiten(byte: u8, index: usize, key: u64) -> u8 {
+    let [a, b, c, d, ..] = whitening_key(index, key);
+    let byte = (byte ^ a).wrapping_mul(b | 1);
+    let byte = byte ^ (byte >> 4);
+    byte.wrapping_mul(c | 1).wrapping_add(d)
+}
+
+/// Undoes the permutation of [`whiten`]. 
+const fn unwhiten(byte: u8, index: usize, key: u64) -> u8 {
+    let [a, b, c, d, ..] = whitening_key(index, key);
+    let byte = byte.wrapping_sub(d).wrapping_mul(inverse(c | 1));
+    let byte = byte ^ (byte >> 4);
+    byte.wrapping_mul(inverse(b | 1)) ^ a
+}
+
+/// Derives the key bytes of the [`whiten`] permutation of given index by mixing it with the key. 
+const fn whitening_key(index: usize, key: u64) -> [u8; 8] {
+    let mut mixed = key ^ (index as u64).wrapping_mul(0x9E3779B97F4A7C15);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D049BB133111EB);
+    (mixed ^ (mixed >> 31)).to_le_bytes()
+}
+
+/// Computes the multiplicative inverse of an odd byte modulo 256. 
+const fn inverse(odd: u8) -> u8 {
+    // each Newton step doubles the number of correct low bits, starting from three, so two steps suffice
+    let mut inverse = odd;
+    let mut i = 0;
+
+    while i < 2 {
+        inverse = inverse.wrapping_mul(2u8.wrapping_sub(odd.wrapping_mul(inverse)));
+        i += 1;
+    }
+    inverse
+}
+
+/// Performs the [`whiten`] pass on a buffer of bytes, the first of which has index `start`. 
+fn whiten_slice(bytes: &mut [u8], start: usize, key: u64) {
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = whiten(*byte, start + i, key);
+    }
+}
+
+/// Performs the [`running_code`] on a buffer of bytes, the first of which has index `start`. 
+fn running_code_slice(bytes: &mut [u8], start: usize, seed: u64) {
+    match bytes.len() < RunningMask::THRESHOLD {
+        true => for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = running_code(*byte, start + i, seed);
+        }
+        false => RunningMask::new(start, seed).apply(bytes), 
+    }
 }
 
 #[cfg(test)]
@@ -256,9 +949,14 @@ mod tests {
     use crate::*;
 
     fn round_trip(data: &[u8], settings: Settings) {
-        let encoded = super::encode_with_settings(data, settings);
-        let decoded = super::decode_with_settings(&encoded, settings.checksum);
-        assert_eq!(decoded.as_deref(), Ok(data), "{data:?}, {settings:?}");
+        if let Err(mismatch) = fuzz_roundtrip(data, settings) {
+            panic!("{mismatch}: {data:?}, {settings:?}");
+        }
+    }
+
+    /// Random number generator with a fixed seed, so that failures are reproducible. 
+    fn rng() -> SmallRng {
+        SmallRng::seed_from_u64(7502546294857623797)
     }
 
     fn stress(n: usize) {
@@ -268,8 +966,10 @@ mod tests {
             Checksum::Length2, 
             Checksum::Length3, 
             Checksum::Length4, 
+            Checksum::Length5, 
+            Checksum::Length8, 
         ];
-        let max_words = [None, Some(1), Some(2), Some(3), Some(10), Some(11)];
+        let max_words = [None, Some(1), Some(2), Some(3), Some(10), Some(11), Some(255)];
         let decorates = [true, false];
         let sizes = [0, 1, 2, 3, 10, 16, 30, 31, 32, 64, 100, 250, 509, 510];
 
@@ -279,15 +979,29 @@ mod tests {
                     for decorate in decorates {
                         let settings = Settings {
                             checksum, 
+                            checksum_seed: 0, 
+                            checksum_order: Endianness::Little, 
+                            checksum_chunk: 0, 
                             word_len: max_word, 
+                            min_word_len: None, 
+                            grouping: WordGrouping::Variable, 
+                            separator: ' ', 
+                            pad_syllables: None, 
+                            minimal_breaks: false, 
                             decorate, 
+                            decoration: Decoration::default(), 
+                            uppercase: false, 
+                            smoothing: true, 
+                            seed: 0, 
+                            whitening: None, 
+                            alphabet: Alphabet::Standard, 
                         };
                         round_trip(data, settings);
                     }
                 }
             }
         };
-        let mut rng = SmallRng::seed_from_u64(7502546294857623797);
+        let mut rng = rng();
 
         for size in sizes {
             for _ in 0..n {
@@ -303,4 +1017,1256 @@ mod tests {
     fn stress_medium() {
         stress(500);
     }
+
+    #[test]
+    fn minimal_breaks() {
+        let words = |encoded: &str| encoded.split(' ').count();
+        let settings = Settings {
+            word_len: None, 
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            minimal_breaks: true, 
+            ..Default::default()
+        };
+
+        // every pair of syllables
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                round_trip(&[a, b], settings);
+            }
+        }
+        // long words, where following syllables could complete a longer syllable
+        let mut rng = rng();
+
+        for size in [3, 10, 32, 100, 509] {
+            for _ in 0..200 {
+                let mut data = vec![0; size];
+                rng.fill_bytes(&mut data);
+
+                for word_len in [None, Some(3)] {
+                    for decorate in [false, true] {
+                        for checksum in [Checksum::Disabled, Checksum::Length2] {
+                            let minimal = Settings {
+                                word_len, 
+                                decorate, 
+                                checksum, 
+                                minimal_breaks: true, 
+                                ..Default::default()
+                            };
+                            round_trip(&data, minimal);
+
+                            let conservative = encode_with_settings(&data, Settings {
+                                minimal_breaks: false, 
+                                ..minimal
+                            });
+                            assert!(words(&encode_with_settings(&data, minimal)) <= words(&conservative));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn delimiterless() {
+        // stripping the word breaks changes the parse
+        let decode = |string| decode_with_settings(string, Checksum::Disabled);
+        assert_ne!(decode("ous"), decode("o us"));
+
+        let mut rng = rng();
+
+        for size in [0, 1, 2, 3, 10, 32, 100, 509] {
+            for _ in 0..100 {
+                let mut data = vec![0; size];
+                rng.fill_bytes(&mut data);
+
+                for minimal_breaks in [false, true] {
+                    for word_len in [None, Some(3)] {
+                        let settings = Settings {
+                            word_len, 
+                            minimal_breaks, 
+                            decorate: true, 
+                            ..Default::default()
+                        };
+                        let encoded = encode_delimiterless(&data, settings);
+                        let decoded = decode_delimiterless(&encoded, DecodeSettings::from(settings));
+
+                        assert!(encoded.chars().all(|char| char.is_ascii_alphabetic()));
+                        assert_eq!(decoded.as_deref(), Ok(&data[..]), "{encoded}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn auto_checksum() {
+        // every total length is recovered from exactly one payload length
+        let mut totals = vec![None; 1100];
+
+        for payload_len in 0..1000 {
+            let total_len = payload_len + Checksum::Auto.len_for(payload_len);
+            assert_eq!(totals[total_len], None);
+            totals[total_len] = Some(payload_len);
+        }
+        for (total_len, payload_len) in totals[..1000].iter().enumerate() {
+            assert_eq!(Checksum::Auto.payload_len(total_len), *payload_len, "{total_len}");
+        }
+
+        let settings = Settings {
+            checksum: Checksum::Auto, 
+            ..Default::default()
+        };
+        let mut rng = rng();
+
+        for len in 0..200 {
+            let mut data = vec![0; len];
+            rng.fill_bytes(&mut data);
+            round_trip(&data, settings);
+
+            let encoded = encode_with_settings(&data, settings);
+            let syllables = decode_with_settings(&encoded, Checksum::Disabled).unwrap().len();
+            assert_eq!(syllables, len + Checksum::Auto.len_for(len));
+            assert_eq!(verify(&encoded, Checksum::Auto), Ok(()));
+            assert_eq!(decode_prefix(&encoded, Checksum::Auto, len), Ok(data.clone()));
+            let fingerprint = decode_with_settings(crate::fingerprint(&data, settings), Checksum::Disabled);
+            assert_eq!(fingerprint.map(|fingerprint| fingerprint.len()), Ok(Checksum::Auto.len_for(len)));
+
+            let tokens = encode_iter(&data, settings)
+                .filter(|token| matches!(token, Token::Syllable(_)))
+                .count();
+            assert_eq!(tokens, syllables);
+        }
+        // dropping a syllable from a payload of 16 bytes yields an impossible length
+        let encoded = encode_with_settings([7; 16], Settings {
+            word_len: Some(1), 
+            ..settings
+        });
+        let truncated = &encoded[..encoded.rfind(' ').unwrap()];
+        assert_eq!(decode_with_settings(truncated, Checksum::Auto), Err(InvalidData::Checksum));
+        assert_eq!(decode_with_settings("", Checksum::Auto), Err(InvalidData::TooShort));
+    }
+
+    #[test]
+    fn long_words() {
+        // a syllable that can be repeated without parsing ambiguities, so that words are only broken due to
+        // their length
+        let byte = (0..=u8::MAX)
+            .find(|&byte| !crate::would_break(syllable(byte), byte))
+            .unwrap();
+        let data = [byte; 1000];
+        let settings = |word_len, min_word_len, grouping| Settings {
+            word_len, 
+            min_word_len, 
+            grouping, 
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        let cases = [
+            (settings(None, None, WordGrouping::Variable), 1), 
+            (settings(Some(0), None, WordGrouping::Variable), 1), 
+            (settings(None, Some(255), WordGrouping::Variable), 1), 
+            (settings(Some(255), None, WordGrouping::Variable), 4), 
+            (settings(Some(255), Some(255), WordGrouping::Variable), 4), 
+            (settings(Some(3), None, WordGrouping::Fixed(0)), 1), 
+            (settings(None, None, WordGrouping::Fixed(255)), 4), 
+        ];
+        for (settings, words) in cases {
+            round_trip(&data, settings);
+            let encoded = encode_with_settings(data, settings);
+            assert_eq!(encoded.split(' ').count(), words, "{settings:?}");
+            assert_eq!(crate::encode_const::<4096>(&data, settings).as_str(), encoded);
+        }
+    }
+
+    #[test]
+    fn min_word_len() {
+        let data = b"it's such a beautiful day";
+
+        for min_word_len in [None, Some(0), Some(1), Some(2), Some(3), Some(5)] {
+            let settings = Settings {
+                min_word_len, 
+                ..Default::default()
+            };
+            round_trip(data, settings);
+        }
+    }
+
+    #[test]
+    fn presets() {
+        for settings in [Settings::compact(), Settings::readable(), Settings::robust()] {
+            assert_eq!(settings.validate(), Ok(()));
+            round_trip(b"it's such a beautiful day", settings);
+        }
+    }
+
+    #[test]
+    fn max_payload_for() {
+        let mut rng = rng();
+        let decorated = Settings {
+            decorate: true, 
+            ..Default::default()
+        };
+        let padded = Settings {
+            pad_syllables: Some('-'), 
+            ..Default::default()
+        };
+        for settings in [Settings::default(), Settings::compact(), Settings::robust(), decorated, padded] {
+            for output_len in [60, 200, 1000] {
+                let payload_len = crate::max_payload_for(output_len, settings);
+                let mean = (0..100)
+                    .map(|_| {
+                        let mut data = vec![0; payload_len];
+                        rng.fill_bytes(&mut data);
+                        encode_with_settings(&data, settings).len()
+                    })
+                    .sum::<usize>() / 100;
+
+                // within 10% of the target on average
+                assert!(mean.abs_diff(output_len) * 10 <= output_len, "{mean} vs {output_len}: {settings:?}");
+            }
+        }
+        assert_eq!(crate::max_payload_for(0, Settings::default()), 0);
+        assert_eq!(crate::max_payload_for(1, decorated), 0);
+        assert!(crate::max_payload_for(usize::MAX, Settings::default()) > 0);
+
+        // a minimum word length doesn't add length-breaks when they're disabled
+        for word_len in [None, Some(0)] {
+            let settings = |min_word_len| Settings {
+                word_len, 
+                min_word_len, 
+                ..Default::default()
+            };
+            let unbounded = crate::max_payload_for(1000, settings(None));
+            assert_eq!(crate::max_payload_for(1000, settings(Some(2))), unbounded);
+            assert!(crate::max_payload_for(1000, Settings::default()) < unbounded);
+        }
+    }
+
+    #[test]
+    fn encode_const() {
+        const SETTINGS: Settings = Settings {
+            word_len: Some(3), 
+            checksum: Checksum::Length1, 
+            ..Settings::compact()
+        };
+        const ENCODED: ConstString<64> = crate::encode_const(b"aftersun", SETTINGS);
+        assert_eq!(SETTINGS, Settings::default());
+        assert_eq!(ENCODED.as_str(), encode(b"aftersun"));
+
+        let mut rng = rng();
+        let variants = [
+            Settings::compact(), 
+            Settings::default(), 
+            Settings { word_len: Some(1), checksum: Checksum::Auto, ..Default::default() }, 
+            Settings { min_word_len: Some(4), checksum: Checksum::Length8, ..Default::default() }, 
+            Settings { grouping: WordGrouping::Fixed(4), separator: '·', ..Default::default() }, 
+            Settings { checksum_seed: 7, checksum_order: Endianness::Big, ..Default::default() }, 
+            Settings { uppercase: true, smoothing: false, ..Default::default() }, 
+            Settings { seed: 0x0123456789abcdef, ..Default::default() }, 
+        ];
+        for settings in variants {
+            for size in [0, 1, 2, 16, 100] {
+                let mut data = vec![0; size];
+                rng.fill_bytes(&mut data);
+
+                let encoded: ConstString<1024> = crate::encode_const(&data, settings);
+                assert_eq!(encoded.as_str(), encode_with_settings(&data, settings), "{settings:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_settings() {
+        let settings = |word_len, min_word_len| Settings {
+            word_len, 
+            min_word_len, 
+            ..Default::default()
+        };
+        assert_eq!(settings(None, Some(5)).validate(), Ok(()));
+        assert_eq!(settings(Some(3), Some(3)).validate(), Ok(()));
+        assert_eq!(settings(Some(0), Some(5)).validate(), Ok(()));
+        let error = SettingsError::MinExceedsMax { min: 5, max: 3 };
+        assert_eq!(settings(Some(3), Some(5)).validate(), Err(error));
+        assert_eq!(Settings::builder().word_len(3).min_word_len(5).build(), Err(error));
+        assert_eq!(Settings::builder().word_len(None).min_word_len(5).build(), Ok(settings(None, Some(5))));
+
+        // a zero word length disables word-breaks due to length
+        let data = b"it's such a beautiful day";
+        let encode = |word_len| encode_with_settings(data, settings(word_len, None));
+        assert_eq!(encode(Some(0)), encode(None));
+        assert!(encode(Some(0)).split(' ').count() < encode(Some(3)).split(' ').count());
+
+        // a minimum exceeding the maximum is tolerated when encoding
+        round_trip(data, settings(Some(3), Some(5)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "undecodable")]
+    fn encode_invalid_settings() {
+        encode_with_settings(b"aftersun", Settings {
+            separator: 'x', 
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn separator() {
+        let data = b"it's such a beautiful day";
+        let spaced = Settings {
+            grouping: WordGrouping::Fixed(4), 
+            ..Default::default()
+        };
+        let spaced = encode_with_settings(data, spaced);
+
+        for separator in ['-', '_', '·', '\n'] {
+            let settings = Settings {
+                grouping: WordGrouping::Fixed(4), 
+                separator, 
+                ..Default::default()
+            };
+            let encoded = encode_with_settings(data, settings);
+            assert_eq!(settings.validate(), Ok(()));
+            assert_eq!(encoded, spaced.replace(' ', separator.encode_utf8(&mut [0; 4])));
+            assert!(encoded.len() <= encoded_len(data.len(), settings));
+            assert_eq!(decode_with_settings(&encoded, settings).unwrap(), data);
+        }
+        let settings = Settings {
+            separator: 'x', 
+            ..Default::default()
+        };
+        assert_eq!(settings.validate(), Err(SettingsError::Separator { separator: 'x' }));
+
+        // zero-width characters are ignored within words, so they can't stand in for a word-break between
+        // syllables that would otherwise be parsed as one
+        let ous = decode_with_settings("ous", Checksum::Disabled).unwrap();
+        let o_us = decode_with_settings("o us", Checksum::Disabled).unwrap();
+        let encoded = encode_with_settings(&o_us, Settings {
+            checksum: Checksum::Disabled, 
+            ..Default::default()
+        });
+        assert_eq!(decode_with_settings(&encoded, Checksum::Disabled), Ok(o_us.clone()));
+
+        for separator in ['\u{200B}', '\u{2060}', '\u{FEFF}', '\u{AD}', '\u{301}'] {
+            let settings = Settings {
+                separator, 
+                ..Default::default()
+            };
+            assert_eq!(settings.validate(), Err(SettingsError::Separator { separator }));
+            let joined = encoded.replace(' ', separator.encode_utf8(&mut [0; 4]));
+            assert_eq!(decode_with_settings(&joined, Checksum::Disabled), Ok(ous.clone()));
+
+            let settings = Settings {
+                pad_syllables: Some(separator), 
+                ..Default::default()
+            };
+            assert_eq!(settings.validate(), Err(SettingsError::Padding { padding: separator }));
+        }
+    }
+
+    #[test]
+    fn pad_syllables() {
+        let data = b"it's such a beautiful day";
+
+        for pad in ['_', '·'] {
+            for decorate in [false, true] {
+                let settings = Settings {
+                    pad_syllables: Some(pad), 
+                    checksum: Checksum::Length2, 
+                    decorate, 
+                    ..Default::default()
+                };
+                let encoded = encode_with_settings(data, settings);
+                let letters = encoded.chars().filter(|char| char.is_alphabetic() || *char == pad).count();
+
+                assert_eq!(letters, 4 * (data.len() + 2), "{encoded}");
+                round_trip(data, settings);
+            }
+        }
+        let settings = Settings {
+            pad_syllables: Some('x'), 
+            ..Default::default()
+        };
+        assert_eq!(settings.validate(), Err(SettingsError::Padding { padding: 'x' }));
+    }
+
+    #[test]
+    fn uppercase() {
+        let data = b"it's such a beautiful day";
+
+        for decorate in [false, true] {
+            let settings = Settings {
+                decorate, 
+                ..Default::default()
+            };
+            let upper = Settings {
+                uppercase: true, 
+                ..settings
+            };
+            let encoded = encode_with_settings(data, upper);
+            assert_eq!(encoded, encode_with_settings(data, settings).to_ascii_uppercase());
+            assert!(!encoded.chars().any(char::is_lowercase));
+            assert_eq!(decode_with_settings(&encoded, settings).as_deref(), Ok(&data[..]));
+        }
+    }
+
+    #[test]
+    fn fixed_grouping_bytes() {
+        let data = b"it's such a beautiful day";
+        let settings = Settings {
+            grouping: WordGrouping::Fixed(4), 
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        // each group encodes the same as the bytes it corresponds to on their own
+        let groups: Vec<_> = data
+            .chunks(4)
+            .map(|chunk| encode_with_settings(chunk, settings))
+            .collect();
+        assert_eq!(encode_with_settings(data, settings), groups.join(" "));
+    }
+
+    #[test]
+    fn fixed_grouping() {
+        let data = b"it's such a beautiful day";
+
+        for n in [0, 1, 2, 3, 7] {
+            let settings = Settings {
+                grouping: WordGrouping::Fixed(n), 
+                ..Default::default()
+            };
+            let mut syllables = 0;
+            let mut word_len = 0;
+
+            for token in encode_iter(data, settings) {
+                match token {
+                    Token::Syllable(_) => {
+                        if n != 0 && syllables != 0 && syllables % n as usize == 0 {
+                            assert_eq!(word_len, 0, "{n}");
+                        }
+                        syllables += 1;
+                        word_len += 1;
+                    }
+                    Token::Break(_) => word_len = 0, 
+                }
+                assert!(n == 0 || word_len <= n, "{n}");
+            }
+            round_trip(data, settings);
+        }
+    }
+
+    #[test]
+    fn decoration_thresholds() {
+        let data = [33; 100];
+        let settings = |comma_threshold, period_threshold| Settings {
+            decorate: true, 
+            decoration: Decoration {
+                comma_threshold, 
+                period_threshold, 
+                ..Default::default()
+            }, 
+            ..Default::default()
+        };
+        let punctuation = |settings| {
+            let encoded = encode_with_settings(data, settings);
+            round_trip(&data, settings);
+            encoded.chars().filter(|&char| char == '.' || char == ',').count()
+        };
+
+        assert_eq!(punctuation(settings(0, 32)), 1);
+        assert!(punctuation(settings(14, 19)) < punctuation(settings(16, 16)));
+        assert_eq!(settings(16, 16).validate(), Ok(()));
+        assert_eq!(settings(17, 16).validate(), Err(SettingsError::Thresholds { comma: 17, period: 16 }));
+
+        let periodic = Settings {
+            decoration: Decoration {
+                mode: DecorationMode::Periodic { sentence_len: 3, clause_len: 2 }, 
+                ..settings(17, 16).decoration
+            }, 
+            ..settings(17, 16)
+        };
+        assert_eq!(periodic.validate(), Ok(()));
+    }
+
+    #[test]
+    fn decoration_delimiters() {
+        let data = b"it's such a beautiful day";
+        let settings = |period, comma| Settings {
+            decorate: true, 
+            decoration: Decoration {
+                period, 
+                comma, 
+                ..Default::default()
+            }, 
+            ..Default::default()
+        };
+        for (period, comma) in [("! ", "; "), ("\u{3002}", "\u{3001}"), (".", " ")] {
+            assert_eq!(settings(period, comma).validate(), Ok(()));
+            round_trip(data, settings(period, comma));
+        }
+        for period in ["", " etc. ", "\u{200B}", "\u{301}\u{AD}"] {
+            assert_eq!(settings(period, ", ").validate(), Err(SettingsError::Period { period }));
+        }
+        for comma in ["", "e, ", "\u{FEFF}"] {
+            assert_eq!(settings(". ", comma).validate(), Err(SettingsError::Comma { comma }));
+        }
+    }
+
+    #[test]
+    fn periodic_decoration() {
+        let settings = Settings {
+            decorate: true, 
+            decoration: Decoration {
+                mode: DecorationMode::Periodic { sentence_len: 3, clause_len: 2 }, 
+                ..Default::default()
+            }, 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(b"it's such a beautiful day", settings);
+        let words: Vec<_> = encoded.split(' ').collect();
+
+        for (i, word) in words[..words.len() - 1].iter().enumerate() {
+            let expected = match i + 1 {
+                n if n % 3 == 0 => Some('.'), 
+                n if n % 2 == 0 => Some(','), 
+                _ => None, 
+            };
+            assert_eq!(word.chars().last().filter(|c| !c.is_alphabetic()), expected, "{encoded}");
+        }
+        round_trip(b"it's such a beautiful day", settings);
+    }
+
+    #[test]
+    fn checksum_delimiter() {
+        let data = b"it's such a beautiful day";
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            decorate: true, 
+            decoration: Decoration {
+                checksum: Some(" -- "), 
+                ..Default::default()
+            }, 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(data, settings);
+        let (payload, checksum) = encoded.split_once(" -- ").unwrap();
+        let decode = |string| decode_with_settings(string, Checksum::Disabled);
+
+        assert_eq!(decode(payload).as_deref(), Ok(&data[..]));
+        assert_eq!(decode(checksum).map(|checksum| checksum.len()), Ok(2));
+        assert!(encoded.len() <= encoded_len(data.len(), settings));
+        round_trip(data, settings);
+
+        // no delimiter without a checksum, when not decorating, or for empty data
+        let undecorated = Settings {
+            decorate: false, 
+            ..settings
+        };
+        let unchecked = Settings {
+            checksum: Checksum::Disabled, 
+            ..settings
+        };
+        assert!(!encode_with_settings(data, undecorated).contains("--"));
+        assert!(!encode_with_settings(data, unchecked).contains("--"));
+        assert!(!encode_with_settings([], settings).contains("--"));
+
+        assert_eq!(settings.validate(), Ok(()));
+        for delimiter in ["", " check ", "\u{200B}"] {
+            let settings = Settings {
+                decoration: Decoration {
+                    checksum: Some(delimiter), 
+                    ..Default::default()
+                }, 
+                ..settings
+            };
+            assert_eq!(settings.validate(), Err(SettingsError::ChecksumDelimiter { delimiter }));
+        }
+    }
+
+    #[test]
+    fn encode_writer() {
+        use std::io::Write;
+
+        let mut rng = SmallRng::seed_from_u64(3204941287635719851);
+        let mut data = vec![0; 3000];
+        rng.fill_bytes(&mut data);
+        let chunked = Settings {
+            checksum: Checksum::Auto, 
+            checksum_chunk: 100, 
+            ..Settings::readable()
+        };
+
+        for settings in [Settings::default(), Settings::compact(), Settings::readable(), chunked] {
+            let expected = encode_with_settings(&data, settings);
+
+            for write_len in [1, 7, 300, 3000] {
+                let mut writer = EncodeWriter::new(Vec::new(), settings);
+
+                for chunk in data.chunks(write_len) {
+                    writer.write_all(chunk).unwrap();
+                    writer.flush().unwrap();
+                }
+                let output = writer.finish().unwrap();
+                assert_eq!(String::from_utf8(output).unwrap(), expected, "{settings:?}");
+            }
+            let mut writer = EncodeWriter::new(Vec::new(), settings);
+            std::io::copy(&mut &data[..], &mut writer).unwrap();
+            assert_eq!(writer.finish().unwrap(), expected.as_bytes());
+        }
+        let writer = EncodeWriter::new(Vec::new(), Settings::default());
+        assert_eq!(writer.finish().unwrap(), encode([]).as_bytes());
+    }
+
+    #[test]
+    fn tokens() {
+        let data = b"it's such a beautiful day";
+
+        for decorate in [false, true] {
+            for (checksum, checksum_chunk) in [
+                (Checksum::Disabled, 0), 
+                (Checksum::Length1, 0), 
+                (Checksum::Length4, 0), 
+                (Checksum::Length2, 7), 
+            ] {
+                let settings = Settings {
+                    checksum, 
+                    checksum_chunk, 
+                    decorate, 
+                    decoration: Decoration {
+                        checksum: Some(" -- "), 
+                        ..Default::default()
+                    }, 
+                    ..Default::default()
+                };
+                let decoration = settings.decoration;
+                let mut rendered = String::new();
+
+                for token in encode_iter(data, settings) {
+                    let text = match token {
+                        Token::Syllable(syllable) => core::str::from_utf8(syllable).unwrap(), 
+                        Token::Break(BreakKind::Space) => " ", 
+                        Token::Break(BreakKind::Comma) => decoration.comma, 
+                        Token::Break(BreakKind::Period) => decoration.period, 
+                        Token::Break(BreakKind::Checksum) => decoration.checksum.unwrap(), 
+                    };
+                    rendered.push_str(text);
+                }
+                let expected = encode_with_settings(data, settings).to_lowercase();
+                assert_eq!(rendered.trim_end(), expected, "{settings:?}");
+            }
+        }
+        let settings = Settings {
+            checksum: Checksum::Disabled, 
+            decorate: true, 
+            ..Default::default()
+        };
+        assert_eq!(encode_iter([], settings).next(), None);
+    }
+
+    #[test]
+    fn break_positions() {
+        use crate::syllables::Lookup;
+
+        let mut rng = rng();
+
+        let chunked = Settings {
+            checksum_chunk: 10, 
+            ..Settings::robust()
+        };
+        let presets = [Settings::default(), Settings::compact(), Settings::readable(), Settings::robust()];
+
+        for settings in presets.into_iter().chain([chunked]) {
+            for size in [0, 1, 2, 16, 100, 300] {
+                let mut data = vec![0; size];
+                rng.fill_bytes(&mut data);
+
+                // the index of the last syllable of each word but the last one
+                let unchecked = DecodeSettings {
+                    checksum: Checksum::Disabled, 
+                    checksum_chunk: 0, 
+                    ..settings.into()
+                };
+                let encoded = encode_with_settings(&data, settings);
+                let mut expected: Vec<usize> = encoded
+                    .split(|char| !settings.alphabet.is_syllable_char(char))
+                    .filter(|word| !word.is_empty())
+                    .scan(0, |syllables, word| {
+                        *syllables += decode_with_settings(word, unchecked).unwrap().len();
+                        Some(*syllables - 1)
+                    })
+                    .collect();
+                expected.pop();
+
+                assert_eq!(crate::break_positions(&data, settings), expected, "{settings:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn bunk_string_semantic() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let decorated = Settings {
+            decorate: true, 
+            checksum: Checksum::Length4, 
+            ..Default::default()
+        };
+        let plain = BunkString::new(b"aftersun".to_vec(), Settings::default());
+        let fancy = BunkString::new(b"aftersun".to_vec(), decorated);
+        let other = BunkString::new(b"after".to_vec(), decorated);
+
+        assert_ne!(plain.to_string(), fancy.to_string());
+        assert_eq!(plain, fancy);
+        assert_ne!(plain, other);
+        assert!(other < plain);
+        assert_eq!(fancy.clone().canonical().to_string(), plain.to_string());
+        assert_eq!(HashSet::from([plain.clone(), fancy.clone(), other.clone()]).len(), 2);
+        assert_eq!(BTreeSet::from([plain, fancy, other.clone()]).first(), Some(&other));
+    }
+
+    #[test]
+    fn entropy_table() {
+        let mut sorted = ENTROPY_TABLE;
+        sorted.sort_unstable();
+        assert!(sorted.iter().copied().eq(0..=u8::MAX));
+
+        // the documented formula matches the running code
+        let seed: u64 = 0x0123456789abcdef;
+        for i in 0..1000 {
+            let code = ENTROPY_TABLE[(i as u8 ^ seed.rotate_right(8 * (i % 8) as u32) as u8) as usize];
+            assert_eq!(running_code(0, i, seed), code);
+        }
+    }
+
+    #[test]
+    fn custom_table() {
+        // every string of 1-5 letters from "abc"; many syllables are prefixes of others
+        let syllables: Vec<String> = (1..=5)
+            .flat_map(|len| (0..3usize.pow(len)).map(move |n| {
+                (0..len).map(|i| (b'a' + (n / 3usize.pow(i) % 3) as u8) as char).collect()
+            }))
+            .take(256)
+            .collect();
+        let table = Table::from_syllables(core::array::from_fn(|i| syllables[i].as_str())).unwrap();
+        assert_eq!(table.validate(), Ok(()));
+        let mut rng = rng();
+
+        for size in [0, 1, 2, 3, 10, 32, 100] {
+            let mut data = vec![0; size];
+            rng.fill_bytes(&mut data);
+
+            for decorate in [false, true] {
+                let settings = Settings {
+                    decorate, 
+                    ..Default::default()
+                };
+                let encoded = encode_with_table(&data, &table, settings);
+                assert_eq!(decode_with_table(&encoded, &table, settings), Ok(data.clone()), "{encoded}");
+            }
+        }
+        let mut invalid: [&str; 256] = core::array::from_fn(|i| syllables[i].as_str());
+        invalid[7] = "aB";
+        assert_eq!(Table::from_syllables(invalid).err(), Some(TableError::InvalidSyllable { index: 7 }));
+        invalid[7] = syllables[3].as_str();
+        assert_eq!(Table::from_syllables(invalid).err(), Some(TableError::Duplicate { first: 3, second: 7 }));
+    }
+
+    #[cfg(feature = "soft-table")]
+    #[test]
+    fn soft_alphabet() {
+        let mut rng = rng();
+
+        for size in [0, 1, 2, 3, 10, 32, 100] {
+            let mut data = vec![0; size];
+            rng.fill_bytes(&mut data);
+
+            for word_len in [None, Some(1), Some(3)] {
+                for decorate in [false, true] {
+                    let settings = Settings {
+                        word_len, 
+                        decorate, 
+                        alphabet: Alphabet::Soft, 
+                        ..Default::default()
+                    };
+                    round_trip(&data, settings);
+                }
+            }
+        }
+        let settings = Settings {
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            alphabet: Alphabet::Soft, 
+            ..Default::default()
+        };
+        assert_eq!(encode_with_settings([0, 5, 10, 200], settings), "abaca bar");
+    }
+
+    #[cfg(feature = "compact-table")]
+    #[test]
+    fn compact_alphabet() {
+        let mut rng = rng();
+
+        for size in [0, 1, 2, 3, 10, 32, 100] {
+            let mut data = vec![0; size];
+            rng.fill_bytes(&mut data);
+
+            for word_len in [None, Some(1), Some(3)] {
+                for decorate in [false, true] {
+                    let settings = Settings {
+                        word_len, 
+                        decorate, 
+                        alphabet: Alphabet::Compact, 
+                        ..Default::default()
+                    };
+                    round_trip(&data, settings);
+                }
+            }
+            // no syllable is a prefix of another, so there are no word-breaks other than due to length
+            let settings = Settings {
+                word_len: None, 
+                alphabet: Alphabet::Compact, 
+                ..Default::default()
+            };
+            assert!(!encode_with_settings(&data, settings).contains(' '));
+
+            let mut decoder = Decoder::new(settings);
+            let encoded = encode_with_settings(&data, Settings{ word_len: Some(2), ..settings });
+            encoded.split_inclusive(' ').try_for_each(|fragment| decoder.push(fragment)).unwrap();
+            assert_eq!(decoder.finish(), Ok(data));
+        }
+    }
+
+    #[test]
+    fn smoothing() {
+        let settings = Settings {
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        assert_eq!(encode_with_settings([0, 0, 0, 0], settings), "uuu u");
+        round_trip(b"it's such a beautiful day", settings);
+    }
+
+    #[test]
+    fn checksum_seeds() {
+        let data = b"it's such a beautiful day";
+
+        for checksum in [Checksum::Length1, Checksum::Length4, Checksum::Length8] {
+            let settings = |checksum_seed| Settings {
+                checksum, 
+                checksum_seed, 
+                ..Default::default()
+            };
+            let encoded = encode_with_settings(data, settings(0xDEADBEEF));
+            round_trip(data, settings(0xDEADBEEF));
+
+            for seed in [0, 1, 0xDEADBEEE] {
+                assert_eq!(decode_with_settings(&encoded, settings(seed)), Err(InvalidData::Checksum));
+            }
+        }
+    }
+
+    #[test]
+    fn checksum_order() {
+        let data = b"it's such a beautiful day";
+        let raw = |settings: Settings| {
+            let settings = Settings {
+                smoothing: false, 
+                ..settings
+            };
+            let unchecked = DecodeSettings {
+                checksum: Checksum::Disabled, 
+                smoothing: false, 
+                ..Default::default()
+            };
+            let encoded = encode_with_settings(data, settings);
+            decode_with_settings(encoded, unchecked).unwrap()[data.len()..].to_vec()
+        };
+
+        for checksum in [Checksum::Length4, Checksum::Length8] {
+            let little = Settings {
+                checksum, 
+                ..Default::default()
+            };
+            let big = Settings {
+                checksum_order: Endianness::Big, 
+                ..little
+            };
+            round_trip(data, big);
+
+            let mut reversed = raw(little);
+            reversed.reverse();
+            assert_eq!(raw(big), reversed);
+            let encoded = encode_with_settings(data, big);
+            assert_eq!(decode_with_settings(encoded, little), Err(InvalidData::Checksum));
+        }
+        // shorter checksums take the most significant bytes of the hash
+        let big = |checksum| Settings {
+            checksum, 
+            checksum_order: Endianness::Big, 
+            ..Default::default()
+        };
+        assert_eq!(raw(big(Checksum::Length2)), raw(big(Checksum::Length4))[..2]);
+    }
+
+    #[test]
+    fn checksum_chunks() {
+        let mut rng = SmallRng::seed_from_u64(1804289383846930886);
+        let checksums = [
+            Checksum::Disabled, 
+            Checksum::Length1, 
+            Checksum::Length3, 
+            Checksum::Length8, 
+            Checksum::Auto, 
+        ];
+
+        for checksum in checksums {
+            for checksum_chunk in [1, 2, 7, 16, 50] {
+                for size in [0, 1, 6, 7, 8, 14, 49, 50, 51, 300] {
+                    let mut data = vec![0; size];
+                    rng.fill_bytes(&mut data);
+
+                    for decorate in [false, true] {
+                        let settings = Settings {
+                            checksum, 
+                            checksum_chunk, 
+                            decorate, 
+                            ..Default::default()
+                        };
+                        round_trip(&data, settings);
+                        let encoded = encode_with_settings(&data, settings);
+
+                        // a single chunk is encoded just like without chunks
+                        if size <= checksum_chunk {
+                            let unchunked = Settings {
+                                checksum_chunk: 0, 
+                                ..settings
+                            };
+                            assert_eq!(encoded, encode_with_settings(&data, unchunked));
+                        }
+                        assert_eq!(crate::verify(&encoded, settings), Ok(()));
+                        assert!(crate::verify_reader(encoded.as_bytes(), settings).unwrap());
+
+                        let prefix = crate::decode_prefix(&encoded, settings, 10).unwrap();
+                        assert_eq!(prefix, data[..size.min(10)]);
+                        assert_eq!(crate::decode_bounded(&encoded, settings, size), Ok(data.clone()));
+                        if let Some(max_payload) = size.checked_sub(1) {
+                            assert!(crate::decode_bounded(&encoded, settings, max_payload).is_err());
+                        }
+
+                        let output = crate::decode_verbose(&encoded, settings).unwrap();
+                        assert_eq!(output.payload, data);
+                        assert!(output.checksum_ok);
+
+                        let mut decoder = Decoder::new(settings);
+                        decoder.push(&encoded).unwrap();
+                        assert_eq!(decoder.finish(), Ok(data.clone()));
+                    }
+                }
+            }
+        }
+        let mut data = [0; 16];
+        rng.fill_bytes(&mut data);
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            checksum_chunk: 4, 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(data, settings);
+        assert_eq!(crate::decode_array(&encoded, settings), Ok(data));
+
+        // corrupt the payload or checksum of one chunk in the raw bytes, which aren't smoothed
+        let raw = DecodeSettings {
+            checksum: Checksum::Disabled, 
+            checksum_chunk: 0, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        let raw_settings = Settings {
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        let bytes = decode_with_settings(&encoded, raw).unwrap();
+        assert_eq!(bytes.len(), 4 * (4 + 2));
+
+        for (position, chunk) in [(0, 0), (5, 0), (7, 1), (16, 2), (23, 3)] {
+            let mut corrupt = bytes.clone();
+            corrupt[position] ^= 1;
+            let corrupt = encode_with_settings(&corrupt, raw_settings);
+            let expected = InvalidData::ChunkChecksum { chunk };
+
+            assert_eq!(decode_with_settings(&corrupt, settings), Err(expected));
+            assert_eq!(crate::verify(&corrupt, settings), Err(expected));
+            assert_eq!(crate::decode_array::<16>(&corrupt, settings), Err(expected));
+            assert!(!crate::verify_reader(corrupt.as_bytes(), settings).unwrap());
+            assert!(!crate::decode_verbose(&corrupt, settings).unwrap().checksum_ok);
+        }
+        // swapping chunks fails their checksums, which are seeded by their indices
+        let mut swapped = bytes.clone();
+        swapped[..12].rotate_left(6);
+        let swapped = encode_with_settings(&swapped, raw_settings);
+        assert_eq!(decode_with_settings(swapped, settings), Err(InvalidData::ChunkChecksum { chunk: 0 }));
+    }
+
+    #[test]
+    fn whitening() {
+        for key in [0, 1, 0xDEADBEEF, u64::MAX] {
+            for index in [0, 1, 255, 256, 1 << 40] {
+                for byte in 0..=u8::MAX {
+                    assert_eq!(unwhiten(whiten(byte, index, key), index, key), byte);
+                }
+            }
+        }
+        let raw = DecodeSettings {
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        let data = [0; 1024];
+
+        for smoothing in [false, true] {
+            for checksum_chunk in [0, 100] {
+                let settings = Settings {
+                    checksum: Checksum::Length4, 
+                    checksum_chunk, 
+                    smoothing, 
+                    whitening: Some(0xDEADBEEF), 
+                    ..Default::default()
+                };
+                round_trip(&data, settings);
+                round_trip(b"it's such a beautiful day", settings);
+                let encoded = encode_with_settings(data, settings);
+                assert_eq!(crate::verify(&encoded, settings), Ok(()));
+                assert_eq!(crate::decode_prefix(&encoded, settings, 300), Ok(data[..300].to_vec()));
+
+                // smoothing alone repeats every 256 bytes
+                let bytes = decode_with_settings(&encoded, raw).unwrap();
+                assert_ne!(bytes[..256], bytes[256..512]);
+
+                let other_key = Settings {
+                    whitening: Some(0xDEADBEEE), 
+                    ..settings
+                };
+                assert_ne!(encode_with_settings(data, other_key), encoded);
+                assert!(decode_with_settings(&encoded, other_key).is_err());
+                assert!(decode_with_settings(&encoded, Settings { whitening: None, ..settings }).is_err());
+            }
+        }
+        let smoothed = encode_with_settings(data, Settings::default());
+        let bytes = decode_with_settings(smoothed, raw).unwrap();
+        assert_eq!(bytes[..256], bytes[256..512]);
+
+        const SETTINGS: Settings = Settings {
+            whitening: Some(7), 
+            ..Settings::compact()
+        };
+        assert_eq!(
+            crate::encode_const::<64>(b"aftersun", SETTINGS).as_str(), 
+            encode_with_settings(b"aftersun", SETTINGS), 
+        );
+    }
+
+    #[test]
+    fn seeds() {
+        let data = b"it's such a beautiful day";
+        let seeds = [0, 1, 0xFF00, 0xDEADBEEF, 0x0123456789ABCDEF, u64::MAX];
+        let encodings: Vec<_> = seeds
+            .into_iter()
+            .map(|seed| Settings {
+                seed, 
+                ..Default::default()
+            })
+            .inspect(|&settings| round_trip(data, settings))
+            .map(|settings| encode_with_settings(data, settings))
+            .collect();
+
+        for (i, a) in encodings.iter().enumerate() {
+            for b in &encodings[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_with() {
+        use ::serde::de::{value::{Error, StrDeserializer}, IntoDeserializer};
+
+        // only deserialization is tested, since serde includes no serializer
+        crate::serde_with!(#[allow(dead_code)] mod checked, Settings {
+            checksum: Checksum::Length2, 
+            ..Default::default()
+        });
+        let settings = Settings {
+            checksum: Checksum::Length2, 
+            ..Default::default()
+        };
+        let encoded = encode_with_settings(b"aftersun", settings);
+        let deserializer: StrDeserializer<Error> = encoded.as_str().into_deserializer();
+        let decoded: Vec<u8> = checked::deserialize(deserializer).unwrap();
+        assert_eq!(decoded, b"aftersun");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_decorated() {
+        use ::serde::de::{value::{Error, StrDeserializer}, IntoDeserializer};
+
+        let data = b"it's such a beautiful day";
+        let canonical = encode_with_settings(data, SERDE_SETTINGS);
+        let decorated = encode_with_settings(data, Settings {
+            decorate: true, 
+            ..SERDE_SETTINGS
+        });
+        let edited = [
+            decorated.clone(), 
+            decorated.to_uppercase(), 
+            format!("  {}!\n", decorated.replace(' ', ",\t")), 
+            canonical.replace(' ', " - "), 
+        ];
+        for string in edited {
+            let deserializer: StrDeserializer<Error> = string.as_str().into_deserializer();
+            let decoded: Vec<u8> = crate::deserialize(deserializer).unwrap();
+            assert_eq!(decoded, data, "{string}");
+            assert_eq!(encode_with_settings(&decoded, SERDE_SETTINGS), canonical);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_binary() {
+        use ::serde::{de::{value::Error, Visitor}, forward_to_deserialize_any, Deserializer};
+
+        /// Deserializer of a binary format containing only bytes. 
+        struct Binary<'a>(&'a [u8]);
+
+        impl<'de> Deserializer<'de> for Binary<'de> {
+            type Error = Error;
+
+            fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> core::result::Result<V::Value, Error> {
+                visitor.visit_borrowed_bytes(self.0)
+            }
+
+            fn is_human_readable(&self) -> bool {
+                false
+            }
+
+            forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+                unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+            }
+        }
+        let decoded: Vec<u8> = crate::deserialize(Binary(b"aftersun")).unwrap();
+        assert_eq!(decoded, b"aftersun");
+    }
+
+    #[test]
+    fn would_break() {
+        let settings = Settings {
+            word_len: None, 
+            checksum: Checksum::Disabled, 
+            smoothing: false, 
+            ..Default::default()
+        };
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let encoded = encode_with_settings([a, b], settings);
+                assert_eq!(encoded.contains(' '), super::would_break(syllable(a), b), "{encoded}");
+            }
+        }
+    }
+
+    #[test]
+    fn encodes_to() {
+        let data = b"it's such a beautiful day";
+        let plain = Settings::default();
+        let decorated = Settings {
+            decorate: true, 
+            ..plain
+        };
+        let encoded = encode_with_settings(data, decorated);
+
+        assert!(crate::encodes_to(data, &encoded, decorated));
+        assert!(!crate::encodes_to(data, &encoded, plain));
+        assert!(!crate::encodes_to(data, &encoded.to_lowercase(), decorated));
+        assert_eq!(decode(&encoded), Ok(data.to_vec()));
+    }
+
+    #[test]
+    fn reformat() {
+        let data = b"it's such a beautiful day";
+        let from = Settings {
+            word_len: Some(1), 
+            checksum: Checksum::Length2, 
+            ..Default::default()
+        };
+        let to = Settings {
+            word_len: None, 
+            decorate: true, 
+            ..from
+        };
+        let encoded = encode_with_settings(data, from);
+
+        assert_eq!(crate::reformat(&encoded, Checksum::Length2, to), Ok(encode_with_settings(data, to)));
+        assert_eq!(crate::reformat(&encoded, Checksum::Length1, to), Err(InvalidData::Checksum));
+    }
+
+    #[test]
+    fn wrap() {
+        let data = b"it's such a beautiful day";
+        let encoded = encode_with_settings(data, Settings {
+            decorate: true, 
+            ..Default::default()
+        });
+        for columns in 0..encoded.len() + 2 {
+            let wrapped = super::wrap(&encoded, columns);
+            assert_eq!(wrapped.replace('\n', " "), encoded);
+            assert_eq!(decode(&wrapped).as_deref(), Ok(&data[..]));
+
+            for line in wrapped.lines() {
+                assert!(line.len() <= columns || !line.contains(' '), "{line:?}, {columns}");
+            }
+        }
+    }
+
+    #[test]
+    fn fingerprint() {
+        let data = b"it's such a beautiful day";
+        let mut hash = Fnv1a::new_seeded(7);
+        data.iter().for_each(|&byte| hash.update(byte));
+
+        for checksum in [Checksum::Disabled, Checksum::Length1, Checksum::Length4, Checksum::Length8] {
+            let settings = Settings {
+                checksum, 
+                checksum_seed: 7, 
+                ..Default::default()
+            };
+            let fingerprint = super::fingerprint(data, settings);
+            let decoded = decode_with_settings(&fingerprint, Checksum::Disabled).unwrap();
+            assert_eq!(decoded, hash.bytes(checksum, Endianness::Little)[..checksum.len()]);
+        }
+    }
+
+    #[test]
+    fn running_mask() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut data = vec![0; 1000];
+        rng.fill_bytes(&mut data);
+
+        for (start, seed) in [(0, 0), (3, 0), (255, 0xDEADBEEF), (1000, u64::MAX)] {
+            let expected: Vec<_> = data
+                .iter()
+                .enumerate()
+                .map(|(i, &byte)| running_code(byte, start + i, seed))
+                .collect();
+            let mut coded = data.clone();
+            running_code_slice(&mut coded, start, seed);
+            assert_eq!(coded, expected);
+        }
+        round_trip(&data, Settings::default());
+    }
 }
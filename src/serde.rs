@@ -1,13 +1,51 @@
 #![cfg(feature = "serde")]
 
-use serde::{Deserialize, Deserializer, Serializer};
-use crate::{Checksum, Settings};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use serde::{de::{self, SeqAccess, Visitor}, Deserialize, Deserializer, Serializer};
+use crate::{Alphabet, Checksum, Decoration, DecorationMode, Endianness, Settings, WordGrouping};
 
-/// Hard-coded settings used for Serde serialization. 
-const SETTINGS: Settings = Settings {
-    max_word: Some(3), 
+/// Settings used for Serde serialization with `#[serde(with = "bunk")]`. Use
+/// [`serde_with`](crate::serde_with) for other settings. 
+/// 
+/// Strings serialized this way can also be encoded or decoded outside of Serde using these settings. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::SERDE_SETTINGS;
+/// 
+/// let serialized = bunk::encode_with_settings(b"aftersun", SERDE_SETTINGS);
+/// let decoded = bunk::decode_with_settings(serialized, SERDE_SETTINGS)?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+pub const SERDE_SETTINGS: Settings = Settings {
+    word_len: Some(3), 
+    min_word_len: None, 
+    grouping: WordGrouping::Variable, 
+    separator: ' ', 
+    pad_syllables: None, 
+    minimal_breaks: false, 
     checksum: Checksum::Disabled, 
+    checksum_seed: 0, 
+    checksum_order: Endianness::Little, 
+    checksum_chunk: 0, 
     decorate: false, 
+    decoration: Decoration {
+        period: ". ", 
+        comma: ", ", 
+        checksum: None, 
+        mode: DecorationMode::Hashed, 
+        period_threshold: 19, 
+        comma_threshold: 14, 
+    }, 
+    uppercase: false, 
+    smoothing: true, 
+    seed: 0, 
+    whitening: None, 
+    alphabet: Alphabet::Standard, 
 };
 
 /// Serialize data for Serde using Bunk. 
@@ -15,22 +53,136 @@ pub fn serialize<S>(data: impl AsRef<[u8]>, serializer: S) -> Result<S::Ok, S::E
 where
     S: Serializer
 {
-    let string = crate::encode_with_settings(data, SETTINGS);
-    serializer.serialize_str(&string)
+    serialize_with_settings(data, SERDE_SETTINGS, serializer)
 }
 
 /// Deserialize data from Serde using Bunk. 
 pub fn deserialize<'a, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<Vec<u8>>, 
+    D: Deserializer<'a>, 
+{
+    deserialize_with_settings(deserializer, SERDE_SETTINGS)
+}
+
+/// Serialize data for Serde using Bunk with given [settings](Settings). 
+/// 
+/// Data is only encoded for human-readable formats; other formats get the raw bytes. 
+/// 
+/// Used by modules generated with [`serde_with`](crate::serde_with). 
+pub fn serialize_with_settings<S>(data: impl AsRef<[u8]>, settings: Settings, serializer: S)
+    -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(data.as_ref())
+    }
+    let string = crate::encode_with_settings(data, settings);
+    serializer.serialize_str(&string)
+}
+
+/// Deserialize data from Serde using Bunk with given [settings](Settings). 
+/// 
+/// Data is only decoded for human-readable formats; other formats are expected to contain the raw bytes. 
+/// 
+/// Used by modules generated with [`serde_with`](crate::serde_with). 
+pub fn deserialize_with_settings<'a, T, D>(deserializer: D, settings: Settings) -> Result<T, D::Error>
 where
     T: From<Vec<u8>>, 
     D: Deserializer<'a>, 
 {
     use serde::de::Error;
 
-    let decode = |string| crate::decode_with_settings(string, SETTINGS.checksum)
+    if !deserializer.is_human_readable() {
+        return deserializer
+            .deserialize_byte_buf(BytesVisitor)
+            .map(T::from)
+    }
+    let decode = |string| crate::decode_with_settings(string, settings)
         .map_err(D::Error::custom);
-    
+
     String::deserialize(deserializer)
         .and_then(decode)
         .map(T::from)
 }
+
+/// Visitor of the raw bytes serialized for formats that aren't human-readable. Sequences are accepted for
+/// formats without native support for bytes. 
+struct BytesVisitor;
+
+impl<'a> Visitor<'a> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Generates a module for use with `#[serde(with = "...")]` that encodes using given [settings](Settings). 
+/// 
+/// The settings expression is evaluated in the generated module, which imports everything from its parent
+/// module. 
+/// 
+/// # Examples
+/// 
+/// ```text
+/// use bunk::{Checksum, Settings};
+/// 
+/// bunk::serde_with!(mod checked, Settings {
+///     checksum: Checksum::Length2, 
+///     ..Default::default()
+/// });
+/// 
+/// #[derive(Serialize, Deserialize)]
+/// struct Vault {
+///     #[serde(with = "checked")]
+///     key: Vec<u8>, 
+///     name: String, 
+/// }
+/// ```
+#[macro_export]
+macro_rules! serde_with {
+    ($(#[$attr:meta])* $vis:vis mod $name:ident, $settings:expr $(,)?) => {
+        $(#[$attr])*
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Serialize data for Serde using Bunk. 
+            pub fn serialize<S>(data: impl AsRef<[u8]>, serializer: S)
+                -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer
+            {
+                $crate::serialize_with_settings(data, $settings, serializer)
+            }
+
+            /// Deserialize data from Serde using Bunk. 
+            pub fn deserialize<'a, T, D>(deserializer: D) -> ::core::result::Result<T, D::Error>
+            where
+                T: From<$crate::__private::Vec<u8>>, 
+                D: $crate::__private::serde::Deserializer<'a>, 
+            {
+                $crate::deserialize_with_settings(deserializer, $settings)
+            }
+        }
+    };
+}
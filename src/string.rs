@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt, hash::{Hash, Hasher}, str::FromStr};
+use crate::*;
+
+/// Data paired with the [settings](Settings) used to encode it. 
+/// 
+/// Formatting with [`Display`](fmt::Display) yields the encoded string, and parsing with [`FromStr`] decodes
+/// a string using the default [settings](Settings). This makes Bunk-encoded values usable in generic code,
+/// such as config parsing and command-line arguments. 
+/// 
+/// Equality, ordering and hashing are semantic: they're based on the decoded data alone, not on the
+/// settings or the encoded string. Two strings with the same payload but different decoration therefore
+/// compare equal and hash the same, which makes them suitable as keys of maps and sets. Strings are ordered
+/// by their data, compared lexicographically. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{BunkString, Settings};
+/// 
+/// let string = BunkString::new(b"aftersun".to_vec(), Settings::default());
+/// let encoded = string.to_string();
+/// let parsed: BunkString = encoded.parse()?;
+/// 
+/// assert_eq!(parsed.data(), b"aftersun");
+/// # Ok::<(), bunk::InvalidData>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct BunkString {
+    /// The decoded data. 
+    data: Vec<u8>, 
+    /// The settings used when encoding. 
+    settings: Settings, 
+}
+
+impl BunkString {
+    /// Pairs data with the settings to be used when encoding it. 
+    pub fn new(data: Vec<u8>, settings: Settings) -> BunkString {
+        BunkString { data, settings }
+    }
+
+    /// Returns the decoded data. 
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the settings used when encoding. 
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// Consumes the string and returns the decoded data. 
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns the string with the default [settings](Settings), so that strings that compare equal are
+    /// also displayed the same. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::{BunkString, Settings};
+    /// 
+    /// let decorated = Settings {
+    ///     decorate: true, 
+    ///     ..Default::default()
+    /// };
+    /// let string = BunkString::new(b"aftersun".to_vec(), decorated);
+    /// 
+    /// assert_eq!(string.canonical().to_string(), bunk::encode(b"aftersun"));
+    /// ```
+    pub fn canonical(self) -> BunkString {
+        BunkString::new(self.data, Settings::default())
+    }
+}
+
+impl PartialEq for BunkString {
+    fn eq(&self, other: &BunkString) -> bool {
+        self.data == other.data
+    }
+}
+
+impl Eq for BunkString {}
+
+impl PartialOrd for BunkString {
+    fn partial_cmp(&self, other: &BunkString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BunkString {
+    fn cmp(&self, other: &BunkString) -> Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl Hash for BunkString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl AsRef<[u8]> for BunkString {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<BunkString> for Vec<u8> {
+    fn from(string: BunkString) -> Vec<u8> {
+        string.data
+    }
+}
+
+impl fmt::Display for BunkString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Encoded::new(&self.data, self.settings).fmt(f)
+    }
+}
+
+impl FromStr for BunkString {
+    type Err = InvalidData;
+
+    /// Decodes a string using the default [settings](Settings). 
+    fn from_str(string: &str) -> Result<BunkString> {
+        decode(string).map(|data| BunkString::new(data, Settings::default()))
+    }
+}
@@ -1,27 +1,86 @@
 //! Interface for the syllables and accompanying lookups used when encoding and decoding. 
 
-use std::iter;
 use include_bytes_plus::include_bytes;
+use crate::Alphabet;
+
+/// Length of the longest syllable in any of the built-in tables. 
+pub const MAX_LEN: usize = 4;
+
+/// Static data of the standard alphabet. 
+static STANDARD: Statics = Statics {
+    syllables: include!("../static/syllables.txt"), 
+    translation: &include_bytes!("static/translation.bin"), 
+    base: &include_bytes!("static/dart_base.bin" as u32le), 
+    check: &include_bytes!("static/dart_check.bin" as u32le), 
+};
+
+/// Static data of the soft alphabet. 
+#[cfg(feature = "soft-table")]
+static SOFT: Statics = Statics {
+    syllables: include!("../static/soft_syllables.txt"), 
+    translation: &include_bytes!("static/soft_translation.bin"), 
+    base: &include_bytes!("static/soft_dart_base.bin" as u32le), 
+    check: &include_bytes!("static/soft_dart_check.bin" as u32le), 
+};
+
+/// Static data of the compact alphabet. 
+#[cfg(feature = "compact-table")]
+static COMPACT: Statics = Statics {
+    syllables: include!("../static/compact_syllables.txt"), 
+    translation: &include_bytes!("static/compact_translation.bin"), 
+    base: &include_bytes!("static/compact_dart_base.bin" as u32le), 
+    check: &include_bytes!("static/compact_dart_check.bin" as u32le), 
+};
+
+/// Gets the static data of an alphabet. 
+const fn statics(alphabet: Alphabet) -> &'static Statics {
+    match alphabet {
+        Alphabet::Standard => &STANDARD, 
+        #[cfg(feature = "soft-table")]
+        Alphabet::Soft => &SOFT, 
+        #[cfg(feature = "compact-table")]
+        Alphabet::Compact => &COMPACT, 
+    }
+}
 
 /// Gets the ascii string of a syllable identified by its index. 
-pub const fn get(index: u8) -> &'static [u8] {
-    const SYLLABLES: [&[u8]; 256] = include!("../static/syllables.txt");
-    SYLLABLES[index as usize]
+pub const fn get(alphabet: Alphabet, index: u8) -> &'static [u8] {
+    statics(alphabet).syllables[index as usize]
+}
+
+/// Gets the string of a syllable identified by its index. 
+pub fn get_str(alphabet: Alphabet, index: u8) -> &'static str {
+    core::str::from_utf8(get(alphabet, index)).expect("All syllables are valid UTF-8")
+}
+
+/// Determines whether a character may be part of a syllable of an alphabet. Digits are only allowed in
+/// alphabets whose translation table includes them. 
+pub fn is_syllable_char(alphabet: Alphabet, char: char) -> bool {
+    char.is_alphabetic() && !is_ignorable(char)
+        || char.is_ascii_digit() && statics(alphabet).translation.len() > 26
+}
+
+/// Determines whether a character is ignored within words when decoding, so that it neither ends the word
+/// nor is taken for a letter. These are combining diacritical marks, e.g. accents pasted over letters, and
+/// invisible formatting characters such as zero-width joiners and soft hyphens. 
+pub fn is_ignorable(char: char) -> bool {
+    matches!(char, 
+        '\u{AD}' | '\u{34F}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}' | 
+        '\u{300}'..='\u{36F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}' | 
+        '\u{FE20}'..='\u{FE2F}'
+    )
 }
 
 /// Greedily attempts to finds the longest syllable prefixed to a string. 
 /// 
 /// Returns `(syllable_index, syllable_length)`. 
-pub fn longest_prefix_of(string: &str) -> Option<(u8, usize)> {
-    let mut node = Node::root();
+pub fn longest_prefix_of(alphabet: Alphabet, string: &str) -> Option<(u8, usize)> {
+    let mut node = Node::root(statics(alphabet));
     let mut len = 0;
 
-    for char in string.chars() {
-        let child = char
-            .try_into()
-            .ok()
-            .and_then(|ascii| node.child(ascii));
-        let Some(child) = child else {
+    // non-ascii characters have no transitions, so the string can be walked byte-wise
+    for byte in string.bytes() {
+        let Some(child) = node.child(byte) else {
             break
         };
         node = child;
@@ -30,27 +89,223 @@ pub fn longest_prefix_of(string: &str) -> Option<(u8, usize)> {
     node.syllable().map(|syllable| (syllable, len))    
 }
 
-/// Determines whether a letter is a valid continuation of a syllable, i.e., whether the letter is a valid
-/// transition from the trie node of the syllable. 
-pub fn char_follows(char: u8, syllable: &[u8]) -> bool {
-    syllable.iter()
+/// Like [`longest_prefix_of`], but backs up to the longest syllable passed if the string continues along the
+/// trie past the syllable it's prefixed with. Used to decode strings encoded with
+/// [minimal breaks](crate::Settings::minimal_breaks). 
+/// 
+/// Returns `(syllable_index, syllable_length)`. 
+pub fn backtracking_prefix_of(alphabet: Alphabet, string: &str) -> Option<(u8, usize)> {
+    let mut node = Node::root(statics(alphabet));
+    let mut longest = None;
+
+    for (len, byte) in (1..).zip(string.bytes()) {
+        let Some(child) = node.child(byte) else {
+            break
+        };
+        node = child;
+
+        if let Some(syllable) = node.syllable() {
+            longest = Some((syllable, len));
+        }
+    }
+    longest
+}
+
+/// Determines whether a word-break is needed between two syllables for them to be parsed with
+/// [`backtracking_prefix_of`]. 
+/// 
+/// This is the case if a longer syllable is a prefix of the two joined, or if the two joined are a prefix of
+/// a longer syllable, which the syllables following them might complete. 
+pub fn needs_break(alphabet: Alphabet, preceding: &[u8], syllable: &[u8]) -> bool {
+    let mut node = preceding
+        .iter()
         .copied()
-        .chain(iter::once(char))
-        .try_fold(Node::root(), Node::child)
-        .is_some()
+        .try_fold(Node::root(statics(alphabet)), Node::child)
+        .expect("The preceding syllable is in the trie");
+
+    for &char in syllable {
+        match node.child(char) {
+            Some(child) if child.syllable().is_some() => return true, 
+            Some(child) => node = child, 
+            None => return false, 
+        }
+    }
+    true
+}
+
+/// Finds the syllable closest to a prefix of a string by edit distance. Used to correct typos. 
+/// 
+/// Prefixes of the leading [syllable characters](is_syllable_char) up to one character longer than the
+/// longest syllable are considered. On ties, the longest prefix is preferred, since it consumes more of the
+/// erroneous input. 
+/// 
+/// Returns `(syllable_index, prefix_length)`, or `None` if the string doesn't start with a syllable
+/// character. 
+pub fn closest_prefix_of(alphabet: Alphabet, string: &str) -> Option<(u8, usize)> {
+    let prefixes = string
+        .char_indices()
+        .take_while(|&(_, char)| is_syllable_char(alphabet, char))
+        .take(MAX_LEN + 1)
+        .map(|(index, char)| index + char.len_utf8());
+    let mut closest = None;
+
+    for prefix_len in prefixes {
+        let prefix = &string[..prefix_len];
+
+        for index in 0..=u8::MAX {
+            let distance = edit_distance(prefix, get(alphabet, index));
+
+            if closest.is_none_or(|(_, _, closest)| distance <= closest) {
+                closest = Some((index, prefix_len, distance));
+            }
+        }
+    }
+    closest.map(|(index, prefix_len, _)| (index, prefix_len))
+}
+
+/// Computes the Levenshtein distance between a string and an ascii syllable, ignoring case. 
+fn edit_distance(string: &str, syllable: &[u8]) -> usize {
+    let mut row: [usize; MAX_LEN + 1] = [0, 1, 2, 3, 4];
+    let row = &mut row[..=syllable.len()];
+
+    for (i, char) in string.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &letter) in syllable.iter().enumerate() {
+            let substitution = diagonal + !char.eq_ignore_ascii_case(&(letter as char)) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution
+                .min(row[j] + 1)
+                .min(diagonal + 1);
+        }
+    }
+    row[syllable.len()]
+}
+
+/// Determines whether a character is a valid continuation of a syllable, i.e., whether the letter is a valid
+/// transition from the trie node of the syllable. 
+/// 
+/// Written as a loop so that it can be used by [`encode_const`](crate::encode_const). 
+pub const fn char_follows(alphabet: Alphabet, char: u8, syllable: &[u8]) -> bool {
+    let mut node = Node::root(statics(alphabet));
+    let mut i = 0;
+
+    while i < syllable.len() {
+        node = match node.child(syllable[i]) {
+            Some(child) => child, 
+            None => return false, 
+        };
+        i += 1;
+    }
+    node.child(char).is_some()
+}
+
+/// Table of syllables along with the lookups used when encoding and decoding. 
+/// 
+/// Implemented by [`Alphabet`] for the built-in tables, and by [`&Table`](crate::Table) for custom tables. 
+pub trait Lookup: Copy {
+    /// Gets the ascii string of a syllable identified by its index. 
+    fn get(&self, index: u8) -> &[u8];
+
+    /// Greedily attempts to find the longest syllable prefixed to a string. 
+    /// 
+    /// Returns `(syllable_index, syllable_length)`. 
+    fn longest_prefix_of(&self, string: &str) -> Option<(u8, usize)>;
+
+    /// Like [`Lookup::longest_prefix_of`], but backs up to the longest syllable passed if the string
+    /// continues along the trie past the syllable it's prefixed with. 
+    fn backtracking_prefix_of(&self, string: &str) -> Option<(u8, usize)>;
+
+    /// Determines whether a letter is a valid continuation of a syllable. 
+    fn char_follows(&self, char: u8, syllable: &[u8]) -> bool;
+
+    /// Determines whether a word-break is needed between two syllables for them to be parsed with
+    /// [`Lookup::backtracking_prefix_of`]. 
+    fn needs_break(&self, preceding: &[u8], syllable: &[u8]) -> bool;
+
+    /// Gets the length of the longest syllable. 
+    fn max_len(&self) -> usize;
+
+    /// Determines whether a character may be part of a syllable. All other characters are skipped when
+    /// decoding. 
+    fn is_syllable_char(&self, char: char) -> bool {
+        char.is_alphabetic() && !is_ignorable(char)
+    }
+}
+
+impl Lookup for Alphabet {
+    fn get(&self, index: u8) -> &[u8] {
+        get(*self, index)
+    }
+
+    fn longest_prefix_of(&self, string: &str) -> Option<(u8, usize)> {
+        longest_prefix_of(*self, string)
+    }
+
+    fn backtracking_prefix_of(&self, string: &str) -> Option<(u8, usize)> {
+        backtracking_prefix_of(*self, string)
+    }
+
+    fn char_follows(&self, char: u8, syllable: &[u8]) -> bool {
+        char_follows(*self, char, syllable)
+    }
+
+    fn needs_break(&self, preceding: &[u8], syllable: &[u8]) -> bool {
+        needs_break(*self, preceding, syllable)
+    }
+
+    fn max_len(&self) -> usize {
+        MAX_LEN
+    }
+
+    fn is_syllable_char(&self, char: char) -> bool {
+        is_syllable_char(*self, char)
+    }
+}
+
+/// Static data of a built-in alphabet, generated by [`trie_dumper`](../trie_dumper/). 
+struct Statics {
+    /// The syllables, ordered by the byte they encode. 
+    syllables: [&'static [u8]; 256], 
+    /// Mapping from letter indices (`a` having index 0), followed by digit indices if the alphabet includes
+    /// digits, to the codes representing transitions in the trie. 
+    translation: &'static [u8], 
+    /// The base array of the [double-array trie](https://www.linux.thai.net/~thep/datrie/). 
+    base: &'static [u32], 
+    /// The check array of the [double-array trie](https://www.linux.thai.net/~thep/datrie/). 
+    check: &'static [u32], 
+}
+
+impl Statics {
+    /// Index into the base array. 
+    /// 
+    /// Returns `(is_leaf, base)`; both are stored in the integer. 
+    const fn base(&self, node_id: u32) -> (bool, u32) {
+        split_msb(self.base[node_id as usize])
+    }
+
+    /// Index into the check array. 
+    /// 
+    /// Returns `(has_leaf, check)`; both are stored in the integer. 
+    const fn check(&self, node_id: u32) -> (bool, u32) {
+        split_msb(self.check[node_id as usize])
+    }
 }
 
 /// Represents a node of the trie. 
 /// 
-/// The trie library [crawdad](https://docs.rs/crawdad) was used to generate the base and check arrays which
-/// are stored in static memory, but since it doesn't allow you to step through the trie (what 
-/// [`Node::child`] does), we replace it with our own basic implementation via this struct. 
+/// The base and check arrays are generated by [`trie_dumper`](../trie_dumper/) and stored in static memory;
+/// this struct steps through them one transition at a time (what [`Node::child`] does). The format follows
+/// that of the trie library [crawdad](https://docs.rs/crawdad), which was originally used to generate them. 
 /// 
 /// See [double-array tries](https://www.linux.thai.net/~thep/datrie/) and the
 /// [crawdad source](https://github.com/daac-tools/crawdad/blob/main/src/trie.rs), for more information
 /// on how this all works. 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy)]
 struct Node {
+    /// Static data of the trie. 
+    statics: &'static Statics, 
     /// Index of the node. 
     id: u32, 
     /// The base of the transitions from the node. 
@@ -64,10 +319,11 @@ struct Node {
 
 impl Node {
     /// The root node of the trie, wherefrom all lookups begin. 
-    const fn root() -> Node {
+    const fn root(statics: &'static Statics) -> Node {
         Node {
+            statics, 
             id: 0, 
-            base: base(0).1, 
+            base: statics.base(0).1, 
             is_leaf: false, 
             has_value: false, 
         }
@@ -77,28 +333,31 @@ impl Node {
     fn syllable(self) -> Option<u8> {
         let syllable = match (self.has_value, self.is_leaf) {
             (true, true) => Some(self.base), 
-            (true, false) => Some(base(self.base).1), 
+            (true, false) => Some(self.statics.base(self.base).1), 
             (false, _) => None, 
         };
         syllable.map(|x| x as u8)
     }
 
     /// Perform a given transition to a child node. 
-    fn child(self, char: u8) -> Option<Node> {
-        const TRANSLATION: [u8; 26] = include_bytes!("static/translation.bin");
-
+    const fn child(self, char: u8) -> Option<Node> {
         // translate ascii char code to a mangled code representing the transition
-        let code = char
-            .to_ascii_lowercase()
-            .checked_sub(b'a')
-            .and_then(|code| TRANSLATION.get(code as usize))
-            .map(|&code| code as u32)?;
+        let index = match char.to_ascii_lowercase() {
+            letter @ b'a'..=b'z' => letter - b'a', 
+            digit @ b'0'..=b'9' => digit - b'0' + 26, 
+            _ => return None, 
+        };
+        let code = match index as usize {
+            index if index < self.statics.translation.len() => self.statics.translation[index] as u32, 
+            _ => return None, 
+        };
 
         // compute the child node
         let id = self.base ^ code;
-        let (is_leaf, base) = base(id);
-        let (has_leaf, check) = check(id);
+        let (is_leaf, base) = self.statics.base(id);
+        let (has_leaf, check) = self.statics.check(id);
         let node = Node {
+            statics: self.statics, 
             id, 
             base, 
             is_leaf, 
@@ -106,30 +365,18 @@ impl Node {
         };
 
         // verify that the transition to the child actually exists and if so, return the child
-        (check == self.id).then_some(node)
+        match check == self.id {
+            true => Some(node), 
+            false => None, 
+        }
     }
 }
 
 /// Splits an integer into the most significant bit and the remainder. 
 /// 
-/// Both [`base`] and [`check`] use the MSB as a flag so this exists as a utility to extract that. 
+/// Both [`Statics::base`] and [`Statics::check`] use the MSB as a flag so this exists as a utility to extract
+/// that. 
 const fn split_msb(integer: u32) -> (bool, u32) {
     const MASK: u32 = !0 >> 1;
     (integer & !MASK != 0, integer & MASK)
 }
-
-/// Index into the base array of the [double-array trie](https://www.linux.thai.net/~thep/datrie/). 
-/// 
-/// Returns `(is_leaf, base)`; both are stored in the integer. 
-const fn base(node_id: u32) -> (bool, u32) {
-    const BASE: &[u32] = &include_bytes!("static/dart_base.bin" as u32le);
-    split_msb(BASE[node_id as usize])
-}
-
-/// Index into the check array of the [double-array trie](https://www.linux.thai.net/~thep/datrie/). 
-/// 
-/// Returns `(has_leaf, check)`; both are stored in the integer. 
-const fn check(node_id: u32) -> (bool, u32) {
-    const CHECK: &[u32] = &include_bytes!("static/dart_check.bin" as u32le);
-    split_msb(CHECK[node_id as usize])
-}
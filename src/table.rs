@@ -0,0 +1,236 @@
+use alloc::{string::String, vec, vec::Vec};
+use thiserror::Error;
+use crate::syllables::Lookup;
+
+/// Error type for constructing a custom syllable [table](Table). 
+#[derive(Error, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum TableError {
+    /// A syllable was empty or contained characters other than lowercase ascii letters. 
+    #[error("Syllable {index} is empty or contains characters other than lowercase ascii letters")]
+    InvalidSyllable {
+        /// Index of the syllable. 
+        index: u8, 
+    }, 
+
+    /// Two syllables were identical. 
+    #[error("Syllables {first} and {second} are identical")]
+    Duplicate {
+        /// Index of the first occurrence of the syllable. 
+        first: u8, 
+        /// Index of the second occurrence of the syllable. 
+        second: u8, 
+    }, 
+}
+
+/// Custom table of 256 syllables constructed at runtime. 
+/// 
+/// This allows encoding and decoding with a themed or localized alphabet in place of the built-in one; see
+/// [`encode_with_table`](crate::encode_with_table) and [`decode_with_table`](crate::decode_with_table).
+/// The syllable identified by an index encodes the byte of the same value. Strings encoded with a custom
+/// table must be decoded with the same table. 
+/// 
+/// The lookup structures needed for decoding are constructed in memory, in contrast with the built-in table
+/// for which they are stored in static memory. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings, Table};
+/// 
+/// let syllables: Vec<String> = (0..256)
+///     .map(|i| format!("{}{}", (b'a' + (i / 16) as u8) as char, (b'a' + (i % 16) as u8) as char))
+///     .collect();
+/// let syllables: [&str; 256] = std::array::from_fn(|i| syllables[i].as_str());
+/// let table = Table::from_syllables(syllables)?;
+/// 
+/// let encoded = bunk::encode_with_table(b"aftersun", &table, Settings::default());
+/// let decoded = bunk::decode_with_table(&encoded, &table, Checksum::default())?;
+/// 
+/// assert_eq!(decoded, b"aftersun");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Table {
+    /// The syllables, ordered by the byte they encode. 
+    syllables: Vec<String>, 
+    /// Nodes of the trie used for lookup. The first node is the root. 
+    nodes: Vec<Node>, 
+    /// Length of the longest syllable. 
+    max_len: usize, 
+}
+
+impl Table {
+    /// Constructs a table from 256 syllables, ordered by the byte they encode. 
+    /// 
+    /// Each syllable must be unique and consist of only lowercase ascii letters; they are matched
+    /// case-insensitively when decoding. 
+    /// 
+    /// No other constraints apply: any such table is decodable, since the encoder inserts a word-break
+    /// whenever the next syllable could be parsed as a continuation of the previous one, and the greedy
+    /// decoder otherwise stops at the end of the previous syllable. 
+    pub fn from_syllables(syllables: [&str; 256]) -> Result<Table, TableError> {
+        let mut table = Table {
+            syllables: Vec::with_capacity(256), 
+            nodes: vec![Node::default()], 
+            max_len: 0, 
+        };
+
+        for (index, syllable) in (0..=u8::MAX).zip(syllables) {
+            if syllable.is_empty() || !syllable.bytes().all(|char| char.is_ascii_lowercase()) {
+                return Err(TableError::InvalidSyllable { index })
+            }
+            // insert the syllable into the trie, adding nodes as needed
+            let mut node = 0;
+
+            for char in syllable.bytes() {
+                let transition = (char - b'a') as usize;
+                node = match table.nodes[node].children[transition] {
+                    0 => {
+                        let child = table.nodes.len();
+                        table.nodes.push(Node::default());
+                        table.nodes[node].children[transition] = child as u32;
+                        child
+                    }
+                    child => child as usize, 
+                };
+            }
+            if let Some(first) = table.nodes[node].syllable.replace(index) {
+                return Err(TableError::Duplicate { first, second: index })
+            }
+            table.max_len = table.max_len.max(syllable.len());
+            table.syllables.push(String::from(syllable));
+        }
+        Ok(table)
+    }
+
+    /// Verifies that the table is decodable. 
+    /// 
+    /// Checks the properties relied upon when encoding and decoding: that all syllables are unique and
+    /// consist of only lowercase ascii letters. Returns the offending syllable(s) otherwise. 
+    /// 
+    /// These are enforced by [`Table::from_syllables`], which guarantees that the table is decodable, so
+    /// this holds for every table. It is exposed so that the guarantee can be checked explicitly, e.g. in the
+    /// tests of a custom table. 
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bunk::Table;
+    /// 
+    /// let syllables: [String; 256] = std::array::from_fn(|i| format!("x{}", bunk::syllable(i as u8)));
+    /// let table = Table::from_syllables(std::array::from_fn(|i| syllables[i].as_str()))?;
+    /// 
+    /// assert_eq!(table.validate(), Ok(()));
+    /// # Ok::<(), bunk::TableError>(())
+    /// ```
+    pub fn validate(&self) -> Result<(), TableError> {
+        for (index, syllable) in (0..=u8::MAX).zip(&self.syllables) {
+            if syllable.is_empty() || !syllable.bytes().all(|char| char.is_ascii_lowercase()) {
+                return Err(TableError::InvalidSyllable { index })
+            }
+            // a duplicate leaves only one of the syllables in the trie
+            match self.longest_prefix_of(syllable) {
+                Some((found, _)) if found == index => (), 
+                Some((found, _)) => return Err(TableError::Duplicate {
+                    first: found.min(index), 
+                    second: found.max(index), 
+                }), 
+                None => return Err(TableError::InvalidSyllable { index }), 
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the syllable encoding a byte. 
+    pub fn syllable(&self, byte: u8) -> &str {
+        &self.syllables[byte as usize]
+    }
+
+    /// Performs a transition from a node of the trie, ignoring case. 
+    fn child(&self, node: usize, char: u8) -> Option<usize> {
+        let transition = char
+            .to_ascii_lowercase()
+            .checked_sub(b'a')
+            .filter(|&transition| transition < 26)?;
+        
+        match self.nodes[node].children[transition as usize] {
+            0 => None, 
+            child => Some(child as usize), 
+        }
+    }
+}
+
+impl Lookup for &Table {
+    fn get(&self, index: u8) -> &[u8] {
+        self.syllables[index as usize].as_bytes()
+    }
+
+    fn longest_prefix_of(&self, string: &str) -> Option<(u8, usize)> {
+        let mut node = 0;
+        let mut len = 0;
+
+        // non-ascii characters have no transitions, so the string can be walked byte-wise
+        for byte in string.bytes() {
+            let Some(child) = self.child(node, byte) else {
+                break
+            };
+            node = child;
+            len += 1;
+        }
+        self.nodes[node].syllable.map(|syllable| (syllable, len))
+    }
+
+    fn backtracking_prefix_of(&self, string: &str) -> Option<(u8, usize)> {
+        let mut node = 0;
+        let mut longest = None;
+
+        for (len, byte) in (1..).zip(string.bytes()) {
+            let Some(child) = self.child(node, byte) else {
+                break
+            };
+            node = child;
+
+            if let Some(syllable) = self.nodes[node].syllable {
+                longest = Some((syllable, len));
+            }
+        }
+        longest
+    }
+
+    fn char_follows(&self, char: u8, syllable: &[u8]) -> bool {
+        syllable.iter()
+            .chain([&char])
+            .try_fold(0, |node, &char| self.child(node, char))
+            .is_some()
+    }
+
+    fn needs_break(&self, preceding: &[u8], syllable: &[u8]) -> bool {
+        let mut node = preceding
+            .iter()
+            .try_fold(0, |node, &char| self.child(node, char))
+            .expect("The preceding syllable is in the trie");
+
+        for &char in syllable {
+            match self.child(node, char) {
+                Some(child) if self.nodes[child].syllable.is_some() => return true, 
+                Some(child) => node = child, 
+                None => return false, 
+            }
+        }
+        true
+    }
+
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+/// Node of the trie of a [`Table`]. 
+#[derive(Clone, Copy, Debug, Default)]
+struct Node {
+    /// Indices of the child nodes, one for each lowercase ascii letter. `0` signifies no child, since the
+    /// root is never a child. 
+    children: [u32; 26], 
+    /// The syllable represented by the node, if any. 
+    syllable: Option<u8>, 
+}
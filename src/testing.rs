@@ -0,0 +1,78 @@
+#![cfg(any(feature = "testing", test))]
+
+use alloc::{string::String, vec::Vec};
+use thiserror::Error;
+use crate::{decode_with_settings, encode_with_settings, encoded_len, InvalidData, Settings};
+
+/// Diagnostics of data failing to survive being encoded and then decoded. See [`fuzz_roundtrip`]. 
+#[derive(Error, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The encoded string was longer than the bound given by [`encoded_len`]. 
+    #[error("Encoded string {encoded:?} is {} bytes long, exceeding the bound of {bound}", .encoded.len())]
+    Length {
+        /// The encoded string. 
+        encoded: String, 
+        /// The bound given by [`encoded_len`]. 
+        bound: usize, 
+    }, 
+
+    /// The encoded string couldn't be decoded. 
+    #[error("Encoded string {encoded:?} failed to decode: {error}")]
+    Decode {
+        /// The encoded string. 
+        encoded: String, 
+        /// The error returned when decoding. 
+        error: InvalidData, 
+    }, 
+
+    /// The decoded data differed from the original data. 
+    #[error("Decoded data diverges from the original at byte {offset}; encoded string: {encoded:?}")]
+    Data {
+        /// The encoded string. 
+        encoded: String, 
+        /// The decoded data. 
+        decoded: Vec<u8>, 
+        /// Index of the first byte differing, or the length of the shorter data if one is a prefix of the
+        /// other. 
+        offset: usize, 
+    }, 
+}
+
+/// Encodes and then decodes data using given [settings](Settings), reporting where the round trip fails. 
+/// 
+/// Unlike [`roundtrip`](crate::roundtrip), this returns detailed diagnostics instead of a boolean, making it
+/// suitable for fuzzing combinations of settings with e.g. `proptest` or `cargo-fuzz`. Requires the
+/// `testing` feature. 
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bunk::{Checksum, Settings};
+/// 
+/// let settings = Settings {
+///     checksum: Checksum::Length2, 
+///     decorate: true, 
+///     ..Default::default()
+/// };
+/// bunk::fuzz_roundtrip(b"aftersun", settings)?;
+/// # Ok::<(), bunk::Mismatch>(())
+/// ```
+pub fn fuzz_roundtrip(data: &[u8], settings: Settings) -> Result<(), Mismatch> {
+    let encoded = encode_with_settings(data, settings);
+    let bound = encoded_len(data.len(), settings);
+
+    if encoded.len() > bound {
+        return Err(Mismatch::Length { encoded, bound })
+    }
+    let decoded = match decode_with_settings(&encoded, settings) {
+        Ok(decoded) => decoded, 
+        Err(error) => return Err(Mismatch::Decode { encoded, error }), 
+    };
+    if decoded != data {
+        let offset = core::iter::zip(&decoded, data)
+            .position(|(a, b)| a != b)
+            .unwrap_or(decoded.len().min(data.len()));
+        return Err(Mismatch::Data { encoded, decoded, offset })
+    }
+    Ok(())
+}
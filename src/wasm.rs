@@ -0,0 +1,17 @@
+#![cfg(feature = "wasm")]
+
+use alloc::{string::String, vec::Vec};
+use wasm_bindgen::prelude::*;
+
+/// Encodes data using the default [settings](crate::Settings). Exported to JavaScript as `encode`. 
+#[wasm_bindgen(js_name = encode)]
+pub fn encode(data: &[u8]) -> String {
+    crate::encode(data)
+}
+
+/// Decodes a string using the default [decoding settings](crate::DecodeSettings). Exported to JavaScript as
+/// `decode`, throwing an `Error` if the string can't be decoded. 
+#[wasm_bindgen(js_name = decode)]
+pub fn decode(string: &str) -> Result<Vec<u8>, JsError> {
+    crate::decode(string).map_err(JsError::from)
+}
@@ -1,69 +1,191 @@
-use std::{fs::File, io::Write, iter};
+use std::{collections::{BTreeMap, VecDeque}, fs::File, io::Write, iter};
 
-struct CodeMapper {
-    table: Vec<u32>, 
-    alphabet_size: u32, 
-}
+/// Flag stored in the most significant bit of base and check values. 
+const FLAG: u32 = 1 << 31;
 
-struct Node {
-    base: u32, 
-    check: u32, 
-}
+/// Check value of unused nodes, which never matches the index of a node. Also used for the root. 
+const UNUSED: u32 = !FLAG;
 
-struct Trie {
-    mapper: CodeMapper, 
-    nodes: Vec<Node>, 
+/// Characters allowed in syllables, in the order of their indices in the translation table. Tables using
+/// only letters get a translation table of only the letters. 
+const SYMBOLS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Node of the intermediate trie from which the double-array trie is built. 
+#[derive(Default)]
+struct Node {
+    /// Child nodes by the letter of the transition to them. 
+    children: BTreeMap<u8, Node>, 
+    /// Index of the syllable represented by the node, if any. 
+    value: Option<u32>, 
 }
 
 fn main() {
     const SYLLABLES: [&[u8]; 256] = include!("../../static/syllables.txt");
+    const SOFT_SYLLABLES: [&[u8]; 256] = include!("../../static/soft_syllables.txt");
+    const COMPACT_SYLLABLES: [&[u8]; 256] = include!("../../static/compact_syllables.txt");
 
-    let mut keys: Vec<_> = SYLLABLES
-        .iter()
-        .map(|ascii| std::str::from_utf8(ascii).unwrap())
-        .enumerate()
-        .map(|(i, s)| (s, i as u32))
-        .collect();
-    keys.sort_by_key(|(key, _)| *key);
-
-    let trie = crawdad::Trie::from_records(keys).unwrap();
-    let trie: Trie = unsafe {
-        std::mem::transmute(trie)
-    };
+    dump(&SYLLABLES, "");
+    dump(&SOFT_SYLLABLES, "soft_");
+    dump(&COMPACT_SYLLABLES, "compact_");
+}
 
-    // since there is no guarantee that the memory layouts of our trie and crawdad's trie are identical, we
-    // use some known values as canaries. if these fail, you're going to have to modify crawdad's source to
-    // dump the data
-    assert_eq!(trie.mapper.table['a' as usize], 2);
-    assert_eq!(trie.mapper.table['z' as usize], 21);
-    assert_eq!(trie.mapper.alphabet_size, 27);
-    assert_eq!(trie.nodes[0].base, 0);
-    assert_eq!(trie.nodes[0].check, u32::MAX >> 1);
+/// Builds the double-array trie of a syllable table and dumps its arrays to the static folder, prefixing the 
+/// file names with `prefix`. 
+fn dump(syllables: &[&[u8]; 256], prefix: &str) {
+    let mut root = Node::default();
 
-    // dump code mapper table
-    {
-        let table: Vec<_> = trie.mapper.table[b'a' as usize ..= b'z' as usize]
+    for (value, syllable) in syllables.iter().enumerate() {
+        assert!(syllable.iter().all(|char| SYMBOLS.contains(char)), "Syllables must be lowercase ascii");
+        let node = syllable
             .iter()
-            .copied()
-            .map(|x| x as u8)
-            .collect();
-        File::create("../static/translation.bin")
-            .and_then(|mut file| file.write_all(&table))
-            .unwrap();
+            .fold(&mut root, |node, &char| node.children.entry(char).or_default());
+        assert!(node.value.replace(value as u32).is_none(), "Syllables must be unique");
     }
+    let translation = translation(syllables);
+    let (base, check) = build(&root, &translation);
+
+    // make sure the arrays are read back correctly before writing them
+    verify(syllables, &translation, &base, &check);
+
+    // dump translation table
+    File::create(format!("../static/{prefix}translation.bin"))
+        .and_then(|mut file| file.write_all(&translation))
+        .unwrap();
 
     // dump base and check arrays
     {
-        let (base, check): (Vec<_>, Vec<_>) = trie.nodes
-            .iter()
-            .map(|node| (node.base, node.check))
+        let (base, check): (Vec<_>, Vec<_>) = iter::zip(base, check)
             .flat_map(|(base, check)| iter::zip(base.to_le_bytes(), check.to_le_bytes()))
             .unzip();
-        File::create("../static/dart_base.bin")
+        File::create(format!("../static/{prefix}dart_base.bin"))
             .and_then(|mut file| file.write_all(&base))
             .unwrap();
-        File::create("../static/dart_check.bin")
+        File::create(format!("../static/{prefix}dart_check.bin"))
             .and_then(|mut file| file.write_all(&check))
             .unwrap();
     }
 }
+
+/// Maps each symbol to the code representing transitions by it. Codes are assigned in order of frequency, 
+/// starting at `1`; code `0` is reserved for the transition to the value of a node. 
+/// 
+/// Digits are only included if any syllable contains one. 
+fn translation(syllables: &[&[u8]; 256]) -> Vec<u8> {
+    let has_digits = syllables.iter().copied().flatten().any(u8::is_ascii_digit);
+    let symbols = match has_digits {
+        true => SYMBOLS.len(), 
+        false => 26, 
+    };
+    let mut frequencies = vec![0usize; symbols];
+
+    for char in syllables.iter().copied().flatten() {
+        frequencies[symbol(*char)] += 1;
+    }
+    let mut order: Vec<_> = (0..symbols).collect();
+    order.sort_by_key(|&symbol| std::cmp::Reverse(frequencies[symbol]));
+
+    let mut translation = vec![0; symbols];
+
+    for (code, symbol) in iter::zip(1.., order) {
+        translation[symbol] = code;
+    }
+    translation
+}
+
+/// Gets the index of a symbol in [`SYMBOLS`]. 
+fn symbol(char: u8) -> usize {
+    SYMBOLS.iter().position(|&symbol| symbol == char).unwrap()
+}
+
+/// Builds the base and check arrays of a double-array trie. 
+/// 
+/// The child of a node by a transition is found at the base of the node XOR the code of the transition, and 
+/// the check of the child is the index of its parent. Nodes without children store their value in their 
+/// base, flagged by the MSB. Other nodes with a value store it in the base of their child by code `0`, and 
+/// flag this by the MSB of their check. 
+fn build(root: &Node, translation: &[u8]) -> (Vec<u32>, Vec<u32>) {
+    // every code XOR this mask is at most the mask, so the arrays span all transitions from a node if they
+    // span its base OR the mask
+    let mask = (translation.len() + 1).next_power_of_two() as u32 - 1;
+
+    // leaves XOR their value with a code when looking up a transition, so the arrays must span all values
+    let mut base = vec![0; 256];
+    let mut check = vec![UNUSED; 256];
+    let mut queue = VecDeque::from([(0, root)]);
+
+    while let Some((id, node)) = queue.pop_front() {
+        if node.children.is_empty() {
+            base[id] = node.value.expect("Leaves represent syllables") | FLAG;
+            continue
+        }
+        let transitions: Vec<_> = node.value
+            .map(|_| (0, None))
+            .into_iter()
+            .chain(node.children
+                .iter()
+                .map(|(&char, child)| (translation[symbol(char)] as u32, Some(child)))
+            )
+            .collect();
+
+        // find the smallest base for which all transitions lead to unused nodes. the root is never a child
+        let unused = |id: u32| id != 0 && check.get(id as usize).is_none_or(|&check| check == UNUSED);
+        let node_base = (0..)
+            .find(|&node_base| transitions.iter().all(|&(code, _)| unused(node_base ^ code)))
+            .unwrap();
+
+        // make every transition from the node lead to a node in the arrays
+        let len = (node_base | mask) as usize + 1;
+
+        if base.len() < len {
+            base.resize(len, 0);
+            check.resize(len, UNUSED);
+        }
+        base[id] = node_base;
+
+        for (code, child) in transitions {
+            let child_id = (node_base ^ code) as usize;
+            check[child_id] = id as u32;
+
+            match child {
+                Some(child) => queue.push_back((child_id, child)), 
+                None => {
+                    base[child_id] = node.value.unwrap();
+                    check[id] |= FLAG;
+                }
+            }
+        }
+    }
+    (base, check)
+}
+
+/// Verifies that the arrays are interpreted as intended, by mirroring the lookup performed in `syllables.rs` 
+/// for every syllable and every possible continuation of it. 
+fn verify(syllables: &[&[u8]; 256], translation: &[u8], base: &[u32], check: &[u32]) {
+    let split = |integer: u32| (integer & FLAG != 0, integer & !FLAG);
+    let child = |id: u32, char: u8| {
+        let node_base = split(base[id as usize]).1;
+        let child = node_base ^ translation[symbol(char)] as u32;
+        (split(check[child as usize]).1 == id).then_some(child)
+    };
+    let value = |id: u32| match (split(base[id as usize]), split(check[id as usize]).0) {
+        ((true, value), _) => Some(value), 
+        ((false, node_base), true) => Some(split(base[node_base as usize]).1), 
+        (_, false) => None, 
+    };
+
+    for (index, syllable) in syllables.iter().enumerate() {
+        let id = syllable
+            .iter()
+            .try_fold(0, |id, &char| child(id, char))
+            .expect("Every syllable is in the trie");
+        assert_eq!(value(id), Some(index as u32));
+
+        for &char in &SYMBOLS[..translation.len()] {
+            let continued = syllables
+                .iter()
+                .any(|other| other.len() > syllable.len() && other.starts_with(syllable)
+                    && other[syllable.len()] == char);
+            assert_eq!(child(id, char).is_some(), continued);
+        }
+    }
+}